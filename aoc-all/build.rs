@@ -0,0 +1,20 @@
+//! Links every day listed in Cargo.toml, so `cargo aoc new` only has to add the dependency.
+
+fn main() {
+    println!("cargo::rerun-if-changed=Cargo.toml");
+
+    let manifest = std::fs::read_to_string("Cargo.toml").expect("Failed to read Cargo.toml");
+    let manifest: toml::Table = manifest.parse().expect("Failed to parse Cargo.toml");
+
+    // Unused crates aren't linked, and their registrations would go with them
+    let uses: String = manifest["dependencies"]
+        .as_table()
+        .expect("dependencies should be a table")
+        .keys()
+        .filter(|name| name.starts_with("day"))
+        .map(|name| format!("use {} as _;\n", name.replace('-', "_")))
+        .collect();
+
+    let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("days.rs");
+    std::fs::write(out, uses).expect("Failed to write days.rs");
+}
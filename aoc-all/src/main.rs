@@ -0,0 +1,5 @@
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+fn main() -> aoc::Result<()> {
+    aoc::__run_all()
+}
@@ -0,0 +1,292 @@
+use crate::Day;
+use clap::Args;
+use std::process::Command;
+
+const REPORT_START: &str = "<!-- BENCHMARKS START -->";
+const REPORT_END: &str = "<!-- BENCHMARKS END -->";
+
+#[derive(Args)]
+pub struct BenchArgs {
+    /// Write a table of the results into README.md
+    #[arg(long)]
+    report: bool,
+    /// Save the results as a named baseline
+    #[arg(long, value_name = "NAME")]
+    save_baseline: Option<String>,
+    /// Compare against a named baseline instead of the previous run
+    #[arg(long, value_name = "NAME")]
+    baseline: Option<String>,
+    /// Exit with an error if any benchmark is slower than the baseline by more than this, e.g. `10%`
+    #[arg(long, value_name = "PERCENT", value_parser = parse_percent)]
+    fail_if_slower: Option<f64>,
+    /// Only benchmark one part
+    #[arg(long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Seconds to measure each benchmark for
+    #[arg(long, value_name = "SECS")]
+    measurement_time: Option<f64>,
+    /// Seconds to warm up each benchmark for
+    #[arg(long, value_name = "SECS")]
+    warm_up_time: Option<f64>,
+    /// Number of samples to collect for each benchmark
+    #[arg(long, value_name = "N")]
+    sample_size: Option<usize>,
+}
+
+fn parse_percent(percent: &str) -> Result<f64, String> {
+    percent
+        .trim_end_matches('%')
+        .parse::<f64>()
+        .map(|percent| percent / 100.0)
+        .map_err(|_| format!("invalid percentage '{percent}'"))
+}
+
+struct Estimate {
+    /// Nanoseconds
+    mean: f64,
+    /// Nanoseconds
+    std_dev: f64,
+}
+
+fn run_bench(day: &Day, args: &BenchArgs) -> bool {
+    let mut command = Command::new("cargo");
+    command.args(["run", "--release", "-p", &day.package, "--", "--bench"]);
+    if let Some(name) = &args.save_baseline {
+        command.args(["--save-baseline", name]);
+    }
+    if let Some(name) = &args.baseline {
+        command.args(["--baseline", name]);
+    }
+    if let Some(secs) = args.measurement_time {
+        command.args(["--measurement-time", &secs.to_string()]);
+    }
+    if let Some(secs) = args.warm_up_time {
+        command.args(["--warm-up-time", &secs.to_string()]);
+    }
+    if let Some(samples) = args.sample_size {
+        command.args(["--sample-size", &samples.to_string()]);
+    }
+    // Criterion filters benchmarks by name
+    if let Some(part) = args.part {
+        command.arg(format!("part {part}"));
+    }
+
+    command.status().expect("Failed to run benchmark").success()
+}
+
+/// Reads the latest estimate Criterion saved for a benchmark.
+fn read_estimate(name: &str) -> Option<Estimate> {
+    read_saved_estimate(name, "new")
+}
+
+/// Reads an estimate from one of Criterion's saved runs (`new`, `base` or a named baseline).
+fn read_saved_estimate(name: &str, saved: &str) -> Option<Estimate> {
+    let path = format!("target/criterion/{name}/{saved}/estimates.json");
+    let estimates: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+
+    Some(Estimate {
+        mean: estimates["mean"]["point_estimate"].as_f64()?,
+        std_dev: estimates["std_dev"]["point_estimate"].as_f64()?,
+    })
+}
+
+/// Names of the alternative implementations of a part that Criterion has results for.
+fn alternatives(day: &Day, part: u8) -> Vec<String> {
+    let prefix = format!("{} part {part} ", day.package);
+    let mut alts: Vec<_> = std::fs::read_dir("target/criterion")
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|name| {
+            Some(
+                name.strip_prefix(&prefix)?
+                    .strip_suffix(" (total)")?
+                    .to_string(),
+            )
+        })
+        .collect();
+    alts.sort();
+    alts
+}
+
+/// Every benchmark a day has, with alternative implementations after the main one of each part.
+fn bench_names(day: &Day) -> Vec<String> {
+    let mut names = vec![format!("{} parse", day.package)];
+    for part in 1..=2 {
+        let alts = alternatives(day, part);
+        let implementations = std::iter::once(format!("part {part}"))
+            .chain(alts.iter().map(|alt| format!("part {part} {alt}")));
+        for implementation in implementations {
+            for kind in ["solve only", "total"] {
+                names.push(format!("{} {implementation} ({kind})", day.package));
+            }
+        }
+    }
+    names
+}
+
+fn format_duration(nanos: f64) -> String {
+    match nanos {
+        n if n < 1e3 => format!("{n:.1} ns"),
+        n if n < 1e6 => format!("{:.1} µs", n / 1e3),
+        n if n < 1e9 => format!("{:.2} ms", n / 1e6),
+        n => format!("{:.2} s", n / 1e9),
+    }
+}
+
+/// Builds the report table from whatever Criterion has measured for each day.
+fn build_report(days: &[Day]) -> String {
+    let mut report = String::from(
+        "| Day | Part | Mean | Std. dev. | Total (with parsing) |\n|---|---|---|---|---|\n",
+    );
+    let mut grand_total = 0.0;
+
+    for day in days {
+        // Every part's total includes parsing, so the calendar counts it once per day instead
+        let mut parse = read_estimate(&format!("{} parse", day.package)).map(|parse| parse.mean);
+        for part in 1..=2 {
+            // Alternative implementations are listed under the main one, but don't count towards the total
+            let implementations =
+                std::iter::once(None).chain(alternatives(day, part).into_iter().map(Some));
+            for alt in implementations {
+                let (prefix, label) = match &alt {
+                    Some(alt) => (
+                        format!("{} part {part} {alt}", day.package),
+                        format!("{part} ({alt})"),
+                    ),
+                    None => (format!("{} part {part}", day.package), part.to_string()),
+                };
+                let (Some(solve), Some(total)) = (
+                    read_estimate(&format!("{prefix} (solve only)")),
+                    read_estimate(&format!("{prefix} (total)")),
+                ) else {
+                    continue;
+                };
+
+                if alt.is_none() {
+                    grand_total += solve.mean;
+                    parse.get_or_insert(total.mean - solve.mean);
+                }
+                report.push_str(&format!(
+                    "| {} | {label} | {} | ± {} | {} |\n",
+                    day.num,
+                    format_duration(solve.mean),
+                    format_duration(solve.std_dev),
+                    format_duration(total.mean),
+                ));
+            }
+        }
+        grand_total += parse.unwrap_or(0.0);
+    }
+
+    report.push_str(&format!(
+        "\n**Whole calendar: {}**\n",
+        format_duration(grand_total)
+    ));
+    report
+}
+
+/// Replaces the marked benchmark section of the README, adding one at the end if it's missing.
+fn write_report(days: &[Day]) {
+    let readme = std::fs::read_to_string("README.md").unwrap_or_default();
+    let section = format!("{REPORT_START}\n{}{REPORT_END}", build_report(days));
+
+    let readme = match (readme.find(REPORT_START), readme.find(REPORT_END)) {
+        (Some(start), Some(end)) if start < end => {
+            format!(
+                "{}{section}{}",
+                &readme[..start],
+                &readme[end + REPORT_END.len()..]
+            )
+        }
+        _ => format!("{}\n\n## Benchmarks\n\n{section}\n", readme.trim_end()),
+    };
+
+    std::fs::write("README.md", readme).expect("Failed to write README.md");
+    println!("Wrote benchmark report to README.md");
+}
+
+/// Compares each benchmark against its baseline, printing a table of the changes.
+/// Returns the benchmarks that got slower by more than the threshold.
+fn check_regressions(baselines: &[(String, Estimate)], threshold: f64) -> Vec<&str> {
+    let mut regressions = vec![];
+
+    println!();
+    println!(
+        "{:<40} {:>12} {:>12} {:>10}",
+        "Benchmark", "Baseline", "Current", "Change"
+    );
+    for (name, baseline) in baselines {
+        let Some(current) = read_estimate(name) else {
+            continue;
+        };
+
+        let change = current.mean / baseline.mean - 1.0;
+        let regressed = change > threshold;
+        if regressed {
+            regressions.push(name.as_str());
+        }
+
+        println!(
+            "{:<40} {:>12} {:>12} {:>+9.1}%{}",
+            name,
+            format_duration(baseline.mean),
+            format_duration(current.mean),
+            change * 100.0,
+            if regressed { "  REGRESSED" } else { "" }
+        );
+    }
+
+    regressions
+}
+
+pub fn bench_days(days: &[Day], args: &BenchArgs) -> ! {
+    // Criterion overwrites `base` as it goes, so grab the baselines before running anything
+    let baselines: Vec<_> = if args.fail_if_slower.is_some() {
+        let saved = args.baseline.as_deref().unwrap_or("base");
+        let baselines = days
+            .iter()
+            .flat_map(bench_names)
+            .filter_map(|name| Some((name.clone(), read_saved_estimate(&name, saved)?)))
+            .collect::<Vec<_>>();
+        if baselines.is_empty() {
+            match &args.baseline {
+                Some(name) => eprintln!("No baseline named '{name}' to compare against"),
+                None => eprintln!("No previous run to compare against"),
+            }
+            std::process::exit(1);
+        }
+        baselines
+    } else {
+        vec![]
+    };
+
+    let failed: Vec<_> = days
+        .iter()
+        .filter(|day| !run_bench(day, args))
+        .map(|day| day.package.as_str())
+        .collect();
+
+    if args.report {
+        write_report(days);
+    }
+
+    if !failed.is_empty() {
+        eprintln!("Benchmarks failed for {}", failed.join(", "));
+        std::process::exit(1);
+    }
+
+    if let Some(threshold) = args.fail_if_slower {
+        let regressions = check_regressions(&baselines, threshold);
+        if !regressions.is_empty() {
+            eprintln!(
+                "\n{} benchmark(s) slower than the baseline by more than {:.1}%",
+                regressions.len(),
+                threshold * 100.0
+            );
+            std::process::exit(1);
+        }
+    }
+    std::process::exit(0);
+}
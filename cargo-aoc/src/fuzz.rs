@@ -0,0 +1,138 @@
+use crate::Day;
+use std::path::Path;
+use std::process::Command;
+
+/// The fuzz crate, in the layout cargo-fuzz expects. It stays out of the workspace since it only
+/// builds on nightly.
+const CARGO_TEMPLATE: &str = r#"[package]
+name = "{{package}}-fuzz"
+version = "0.0.0"
+publish = false
+edition = "2024"
+
+[package.metadata]
+cargo-fuzz = true
+
+[dependencies]
+libfuzzer-sys = "0.4"
+aoc = { path = "{{common}}" }
+{{package}} = { path = ".." }
+
+[workspace]
+members = ["."]
+
+[[bin]]
+name = "parts"
+path = "fuzz_targets/parts.rs"
+test = false
+doc = false
+bench = false
+"#;
+
+const TARGET_TEMPLATE: &str = r#"#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+// Links the day's parts in
+use {{crate}} as _;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        aoc::__fuzz({{day}}, input);
+    }
+});
+"#;
+
+/// Crashes are copied to regressions/, which is committed
+const GITIGNORE: &str = "target\ncorpus\nartifacts\ncoverage\n";
+
+/// Where crash inputs are kept for `cargo aoc check` to replay.
+pub fn regressions_dir(day: &Day) -> std::path::PathBuf {
+    Path::new(&day.path).join("fuzz/regressions")
+}
+
+fn create_fuzz_crate(day: &Day, dir: &Path) {
+    // e.g. ../../common from day04/fuzz, ../../../common from 2024/day04/fuzz
+    let common = format!("{}common", "../".repeat(day.path.split('/').count() + 1));
+    let render = |template: &str| {
+        crate::new::render(template, day)
+            .replace("{{package}}", &day.package)
+            .replace("{{common}}", &common)
+    };
+
+    std::fs::create_dir_all(dir.join("fuzz_targets")).expect("Failed to create fuzz directory");
+    for (path, contents) in [
+        ("Cargo.toml", render(CARGO_TEMPLATE)),
+        ("fuzz_targets/parts.rs", render(TARGET_TEMPLATE)),
+        (".gitignore", GITIGNORE.to_string()),
+    ] {
+        std::fs::write(dir.join(path), contents)
+            .unwrap_or_else(|e| panic!("Failed to write {path}: {e}"));
+    }
+    println!("Created {}", dir.display());
+}
+
+/// Copies new crash inputs from cargo-fuzz's artifacts into regressions/, returning how many.
+fn save_regressions(day: &Day, dir: &Path) -> usize {
+    let Ok(artifacts) = std::fs::read_dir(dir.join("artifacts/parts")) else {
+        return 0;
+    };
+    let regressions = regressions_dir(day);
+    std::fs::create_dir_all(&regressions).expect("Failed to create regressions directory");
+
+    let mut saved = 0;
+    for artifact in artifacts.filter_map(Result::ok) {
+        let name = artifact.file_name();
+        // Timeouts and OOMs would hang or kill check, which replays without limits, so they stay
+        // in artifacts/ to look at by hand
+        let is_crash = name.to_string_lossy().starts_with("crash-");
+        let destination = regressions.join(&name);
+        if is_crash && !destination.exists() {
+            std::fs::copy(artifact.path(), destination).expect("Failed to save crash input");
+            saved += 1;
+        }
+    }
+    saved
+}
+
+/// Fuzzes the day's parser and parts with cargo-fuzz, creating the fuzz crate the first time.
+pub fn fuzz_day(day: &Day, max_time: Option<u64>) -> ! {
+    let dir = Path::new(&day.path).join("fuzz");
+    if !dir.join("Cargo.toml").exists() {
+        create_fuzz_crate(day, &dir);
+    }
+
+    let has_cargo_fuzz = Command::new("cargo")
+        .args(["+nightly", "fuzz", "--version"])
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !has_cargo_fuzz {
+        eprintln!("cargo aoc fuzz needs cargo-fuzz and a nightly toolchain:");
+        eprintln!("  rustup toolchain install nightly");
+        eprintln!("  cargo install cargo-fuzz");
+        std::process::exit(1);
+    }
+
+    let max_time = max_time.map(|secs| format!("-max_total_time={secs}"));
+    let mut args = vec!["+nightly", "fuzz", "run", "parts"];
+    if let Some(max_time) = &max_time {
+        args.extend(["--", max_time]);
+    }
+
+    // Ctrl-C stops the fuzzer but not us, so crashes found so far still get saved
+    let _ = ctrlc::set_handler(|| {});
+    let status = Command::new("cargo")
+        .args(&args)
+        .current_dir(&day.path)
+        .status()
+        .expect("Failed to run cargo fuzz");
+
+    let saved = save_regressions(day, &dir);
+    if saved > 0 {
+        println!(
+            "Saved {saved} crashing inputs to {}, which cargo aoc check {} replays",
+            regressions_dir(day).display(),
+            day.num
+        );
+    }
+    std::process::exit(status.code().unwrap_or(1));
+}
@@ -0,0 +1,104 @@
+use chacha20poly1305::aead::{KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key};
+use clap::Subcommand;
+use std::path::{Path, PathBuf};
+
+const KEY_PATH: &str = ".aoc/input.key";
+
+#[derive(Subcommand)]
+pub enum InputCommand {
+    /// Encrypt every cached input into a .enc file that's safe to commit
+    Encrypt,
+    /// Decrypt every .enc file back into the plain input cache
+    Decrypt,
+}
+
+/// Same lookup as the runner: `AOC_INPUT_KEY`, then .aoc/input.key in the current directory,
+/// where the inputs are.
+fn read_key() -> Option<Key> {
+    aoc::__input_key(Path::new(".")).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    })
+}
+
+fn generate_key() -> Key {
+    let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+    std::fs::create_dir_all(".aoc").expect("Failed to create .aoc directory");
+    std::fs::write(KEY_PATH, hex::encode(key) + "\n").expect("Failed to write input key");
+    eprintln!("Generated a new key in {KEY_PATH}, keep it out of git and share it privately");
+    key
+}
+
+/// Lists the files in every year's input cache.
+fn cached_files() -> Vec<PathBuf> {
+    let Ok(years) = std::fs::read_dir(".input") else {
+        return vec![];
+    };
+
+    years
+        .flatten()
+        .filter(|year| year.path().is_dir())
+        .flat_map(|year| std::fs::read_dir(year.path()).expect("Failed to read input cache"))
+        .flatten()
+        .map(|file| file.path())
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+fn encrypt_file(key: &Key, path: &Path) {
+    let input = std::fs::read_to_string(path).expect("Failed to read input");
+    let encrypted_path = path.with_extension("enc");
+
+    // Every encryption gets a new nonce, so only rewrite committed files when the input changed
+    let unchanged = std::fs::read(&encrypted_path)
+        .ok()
+        .and_then(|encrypted| aoc::__decrypt_input(key, &encrypted).ok())
+        .is_some_and(|existing| existing == input);
+    if unchanged {
+        return;
+    }
+
+    let encrypted = aoc::__encrypt_input(key, &input).expect("Failed to encrypt input");
+    std::fs::write(&encrypted_path, encrypted).expect("Failed to write encrypted input");
+    println!("Encrypted {}", encrypted_path.display());
+}
+
+fn decrypt_file(key: &Key, path: &Path) {
+    let encrypted = std::fs::read(path).expect("Failed to read encrypted input");
+    let input = aoc::__decrypt_input(key, &encrypted).unwrap_or_else(|e| {
+        eprintln!("{}: {e}", path.display());
+        std::process::exit(1);
+    });
+
+    let input_path = path.with_extension("");
+    std::fs::write(&input_path, input).expect("Failed to write input");
+    println!("Decrypted {}", input_path.display());
+}
+
+pub fn run(command: &InputCommand) {
+    let is_encrypted = |path: &PathBuf| path.extension().is_some_and(|ext| ext == "enc");
+    // Skip the download throttle marker
+    let is_hidden = |path: &PathBuf| path.file_name().unwrap().to_string_lossy().starts_with('.');
+
+    match command {
+        InputCommand::Encrypt => {
+            let key = read_key().unwrap_or_else(generate_key);
+            for path in cached_files()
+                .iter()
+                .filter(|path| !is_encrypted(path) && !is_hidden(path))
+            {
+                encrypt_file(&key, path);
+            }
+        }
+        InputCommand::Decrypt => {
+            let Some(key) = read_key() else {
+                eprintln!("No input key found, set AOC_INPUT_KEY or add {KEY_PATH}");
+                std::process::exit(1);
+            };
+            for path in cached_files().iter().filter(|path| is_encrypted(path)) {
+                decrypt_file(&key, path);
+            }
+        }
+    }
+}
@@ -3,6 +3,15 @@ use clap::error::ErrorKind;
 use clap::{CommandFactory, Parser, Subcommand};
 use std::io::{Read, Write};
 use std::process::Command;
+use toml_edit::DocumentMut;
+
+mod bench;
+mod fuzz;
+mod input;
+mod new;
+mod read;
+mod step;
+mod watch;
 
 const STYLES: Styles = Styles::styled()
     .header(AnsiColor::BrightGreen.on_default().effects(Effects::BOLD))
@@ -29,45 +38,159 @@ enum Cargo {
 struct AocArgs {
     #[command(subcommand)]
     command: AocCommand,
+    /// Puzzle year, defaults to the year in .aoc/config.toml
+    #[arg(long, global = true)]
+    year: Option<u32>,
 }
 
 #[derive(Subcommand)]
 enum AocCommand {
-    /// Run a day's solution
+    /// Run a day's solution, or every day with `all`
     Run {
         #[arg(value_name = "DAY")]
         day: String,
         /// Use example input
         #[arg(long)]
         example: bool,
+        /// Report allocations and peak memory for parsing and each part
+        #[arg(long)]
+        mem: bool,
+        /// Give up on parts still running after this long, e.g. 30s
+        #[arg(long)]
+        timeout: Option<String>,
+        /// Run parts one at a time
+        #[arg(long)]
+        sequential: bool,
+        /// Run at most this many parts at once, and size rayon's pool to match
+        #[arg(long)]
+        threads: Option<usize>,
+        /// Save frames recorded with aoc::viz to .viz/dayNN
+        #[arg(long)]
+        viz: bool,
+        /// Show output from aoc::debug! and aoc::trace! with the real input too
+        #[arg(long)]
+        verbose: bool,
     },
-    /// Run benchmarks for a day
+    /// Run benchmarks for a day, or every day with `all`
     Bench {
         #[arg(value_name = "DAY")]
         day: String,
+        #[command(flatten)]
+        args: bench::BenchArgs,
     },
     /// Create a new day project
     New {
         #[arg(value_name = "DAY")]
         day: String,
+        /// Template from .aoc/templates to generate the day from
+        #[arg(long)]
+        template: Option<String>,
+    },
+    /// Re-run a day whenever its source or input changes
+    Watch {
+        #[arg(value_name = "DAY")]
+        day: String,
+        /// Use example input
+        #[arg(long)]
+        example: bool,
+    },
+    /// Compare each part against its alternatives and oracles on inputs from its #[generator]
+    Check {
+        #[arg(value_name = "DAY")]
+        day: String,
+        /// Number of random inputs to try
+        #[arg(long)]
+        cases: Option<u32>,
+    },
+    /// Fuzz a day's parser and parts with cargo-fuzz, saving crashes as regression inputs
+    Fuzz {
+        #[arg(value_name = "DAY")]
+        day: String,
+        /// Stop after this many seconds
+        #[arg(long, value_name = "SECS")]
+        max_time: Option<u64>,
+    },
+    /// Step through the frames a day recorded with aoc::viz
+    Step {
+        #[arg(value_name = "DAY")]
+        day: String,
+        /// Part to step through, defaults to the first one with frames
+        #[arg(long)]
+        part: Option<u32>,
+        /// Use example input
+        #[arg(long)]
+        example: bool,
+    },
+    /// Download the puzzle description as Markdown
+    Read {
+        #[arg(value_name = "DAY")]
+        day: String,
+    },
+    /// Manage the encrypted input cache
+    Input {
+        #[command(subcommand)]
+        command: input::InputCommand,
     },
     #[command(external_subcommand)]
     External(Vec<String>),
 }
 
-fn parse_day(day: &str) -> Option<(String, u32)> {
+/// Used when .aoc/config.toml doesn't set a year
+const DEFAULT_YEAR: u32 = 2025;
+
+/// A day crate. Days from the configured year live at `dayNN`, other years at `YYYY/dayNN`.
+struct Day {
+    num: u32,
+    year: u32,
+    package: String,
+    path: String,
+}
+
+fn configured_year() -> u32 {
+    std::fs::read_to_string(".aoc/config.toml")
+        .ok()
+        .and_then(|config| config.parse::<DocumentMut>().ok())
+        .and_then(|config| config.get("year")?.as_integer())
+        .map(|year| year as u32)
+        .unwrap_or(DEFAULT_YEAR)
+}
+
+fn parse_day(day: &str, year: Option<u32>) -> Option<Day> {
     let num: u32 = day.strip_prefix("day").unwrap_or(day).parse().ok()?;
-    Some((format!("day{num:02}"), num))
+    let default_year = configured_year();
+    let year = year.unwrap_or(default_year);
+
+    let (package, path) = if year == default_year {
+        (format!("day{num:02}"), format!("day{num:02}"))
+    } else {
+        (format!("day{num:02}-{year}"), format!("{year}/day{num:02}"))
+    };
+
+    Some(Day {
+        num,
+        year,
+        package,
+        path,
+    })
 }
 
-fn ensure_example_input(day_num: u32) {
-    let example_path = format!(".input/day{}_example", day_num);
+/// Every day crate that exists for the year.
+fn all_days(year: Option<u32>) -> Vec<Day> {
+    (1..=25)
+        .filter_map(|num| parse_day(&num.to_string(), year))
+        .filter(|day| std::path::Path::new(&day.path).join("Cargo.toml").exists())
+        .collect()
+}
+
+fn ensure_example_input(day: &Day) {
+    let input_dir = aoc::__input_dir(std::path::Path::new("."), day.year);
+    let example_path = input_dir.join(format!("day{}_example", day.num));
 
     if std::fs::metadata(&example_path).is_ok() {
         return;
     }
 
-    eprintln!("No example input found for day {}.", day_num);
+    eprintln!("No example input found for day {}.", day.num);
     eprintln!("Paste example input, then press Enter followed by Ctrl+D:");
     eprintln!("---");
 
@@ -75,21 +198,99 @@ fn ensure_example_input(day_num: u32) {
     std::io::stdin()
         .read_to_string(&mut input)
         .expect("Failed to read input");
-    std::fs::create_dir_all(".input").expect("Failed to create .input directory");
+    std::fs::create_dir_all(&input_dir).expect("Failed to create .input directory");
     let mut file = std::fs::File::create(&example_path).expect("Failed to create example file");
     file.write_all(input.as_bytes())
         .expect("Failed to write example input");
 
     eprintln!("---");
-    eprintln!("Saved example to {}", example_path);
+    eprintln!("Saved example to {}", example_path.display());
 }
 
-fn run_day(day: &str, example: bool) -> ! {
-    let args: Vec<&str> = if example {
-        vec!["run", "--release", "-p", day, "--", "--example"]
-    } else {
-        vec!["run", "--release", "-p", day]
-    };
+/// Flags passed through to the day's binary.
+#[derive(Default)]
+struct RunFlags {
+    example: bool,
+    mem: bool,
+    timeout: Option<String>,
+    sequential: bool,
+    threads: Option<String>,
+    viz: bool,
+    verbose: bool,
+}
+
+/// Runs a day's binary, or every day in one process with the `aoc-all` binary.
+fn run_day(day: Option<&str>, flags: &RunFlags) -> ! {
+    let mut args = vec!["run", "--release", "-p", day.unwrap_or("aoc-all")];
+    if flags.mem {
+        // The counting allocator is only compiled in with this feature
+        args.extend(["--features", "aoc/mem"]);
+    }
+    if flags.viz {
+        // As is recording frames
+        args.extend(["--features", "aoc/viz"]);
+    }
+    if flags.example || flags.verbose {
+        // debug! and trace! only print with this feature, so benchmarks never include them
+        args.extend(["--features", "aoc/debug"]);
+    }
+    args.push("--");
+    if day.is_none() {
+        args.extend(["run", "all"]);
+    }
+    if flags.example {
+        args.push("--example");
+    }
+    if flags.mem {
+        args.push("--mem");
+    }
+    if let Some(timeout) = &flags.timeout {
+        args.extend(["--timeout", timeout]);
+    }
+    if flags.viz {
+        args.push("--viz");
+    }
+    if flags.verbose {
+        args.push("--verbose");
+    }
+    if flags.sequential {
+        args.push("--sequential");
+    }
+    if let Some(threads) = &flags.threads {
+        args.extend(["--threads", threads]);
+    }
+
+    // Ctrl-C is for the day, which reports the parts that finished and exits with 130. Waiting for
+    // it keeps that report before the shell prompt and passes the status on.
+    let _ = ctrlc::set_handler(|| {});
+    let status = Command::new("cargo")
+        .args(&args)
+        .status()
+        .expect("Failed to run cargo");
+    std::process::exit(status.code().unwrap_or(1));
+}
+
+/// Runs the day's property checks and fuzzing regressions, built with optimizations but still
+/// checking for overflow.
+fn check_day(day: &Day, cases: Option<u32>) -> ! {
+    let cases = cases.map(|cases| cases.to_string());
+    let regressions = fuzz::regressions_dir(day);
+    let regressions = regressions.to_string_lossy();
+    let mut args = vec![
+        "run",
+        "--profile",
+        "checked",
+        "-p",
+        &day.package,
+        "--",
+        "--check",
+    ];
+    if let Some(cases) = &cases {
+        args.extend(["--cases", cases]);
+    }
+    if std::path::Path::new(regressions.as_ref()).exists() {
+        args.extend(["--regressions", &regressions]);
+    }
 
     let status = Command::new("cargo")
         .args(&args)
@@ -101,19 +302,110 @@ fn run_day(day: &str, example: bool) -> ! {
 fn main() {
     let Cargo::Aoc(args) = Cargo::parse();
 
-    // Extract day and example flag from Run or External
-    let (day_str, example) = match &args.command {
-        AocCommand::Run { day, example } => (day.clone(), *example),
+    if let AocCommand::Input { command } = &args.command {
+        input::run(command);
+        return;
+    }
+
+    if let AocCommand::Bench {
+        day,
+        args: bench_args,
+    } = &args.command
+        && day == "all"
+    {
+        bench::bench_days(&all_days(args.year), bench_args);
+    }
+
+    // Extract day and run flags from Run or External
+    let mut year = args.year;
+    let (day_str, flags) = match &args.command {
+        AocCommand::Run {
+            day,
+            example,
+            mem,
+            timeout,
+            sequential,
+            threads,
+            viz,
+            verbose,
+        } => (
+            day.clone(),
+            RunFlags {
+                example: *example,
+                mem: *mem,
+                timeout: timeout.clone(),
+                sequential: *sequential,
+                threads: threads.map(|threads| threads.to_string()),
+                viz: *viz,
+                verbose: *verbose,
+            },
+        ),
         AocCommand::External(ext_args) => {
             let day = ext_args.first().cloned().unwrap_or_default();
-            let example = ext_args.iter().any(|a| a == "--example");
-            (day, example)
+            // Global args aren't parsed for external subcommands, so pick everything out by hand
+            let value = |flag: &str| {
+                let pos = ext_args.iter().position(|a| a == flag)?;
+                ext_args.get(pos + 1).cloned()
+            };
+            if let Some(y) = value("--year") {
+                year = y.parse().ok();
+            }
+            let flags = RunFlags {
+                example: ext_args.iter().any(|a| a == "--example"),
+                mem: ext_args.iter().any(|a| a == "--mem"),
+                timeout: value("--timeout"),
+                sequential: ext_args.iter().any(|a| a == "--sequential"),
+                threads: value("--threads"),
+                viz: ext_args.iter().any(|a| a == "--viz"),
+                verbose: ext_args.iter().any(|a| a == "--verbose"),
+            };
+            (day, flags)
         }
-        AocCommand::Bench { day } => (day.clone(), false),
-        AocCommand::New { day } => (day.clone(), false),
+        AocCommand::Bench { day, .. } => (day.clone(), RunFlags::default()),
+        AocCommand::New { day, .. } => (day.clone(), RunFlags::default()),
+        AocCommand::Watch { day, example } => (
+            day.clone(),
+            RunFlags {
+                example: *example,
+                ..RunFlags::default()
+            },
+        ),
+        AocCommand::Check { day, .. } => (day.clone(), RunFlags::default()),
+        AocCommand::Fuzz { day, .. } => (day.clone(), RunFlags::default()),
+        AocCommand::Step { day, example, .. } => (
+            day.clone(),
+            RunFlags {
+                example: *example,
+                ..RunFlags::default()
+            },
+        ),
+        AocCommand::Read { day } => (day.clone(), RunFlags::default()),
+        AocCommand::Input { .. } => unreachable!(),
     };
 
-    let Some((day, day_num)) = parse_day(&day_str) else {
+    if matches!(
+        args.command,
+        AocCommand::Run { .. } | AocCommand::External(_)
+    ) && day_str == "all"
+    {
+        // aoc-all only links the configured year's days
+        if let Some(year) = year
+            && year != configured_year()
+        {
+            AocArgs::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    format!(
+                        "run all only runs {}, the year in .aoc/config.toml, not {year}",
+                        configured_year()
+                    ),
+                )
+                .exit();
+        }
+        run_day(None, &flags);
+    }
+
+    let Some(day) = parse_day(&day_str, year) else {
         AocArgs::command()
             .error(
                 ErrorKind::InvalidSubcommand,
@@ -124,38 +416,28 @@ fn main() {
 
     match args.command {
         AocCommand::Run { .. } | AocCommand::External(_) => {
-            if example {
-                ensure_example_input(day_num);
+            if flags.example {
+                ensure_example_input(&day);
             }
-            run_day(&day, example);
+            run_day(Some(&day.package), &flags);
         }
-        AocCommand::Bench { .. } => {
-            let status = Command::new("cargo")
-                .args(["run", "--release", "-p", &day, "--", "--bench"])
-                .status()
-                .expect("Failed to run benchmark");
-            std::process::exit(status.code().unwrap_or(1));
+        AocCommand::Bench { args, .. } => bench::bench_days(&[day], &args),
+        AocCommand::New { template, .. } => new::new_day(&day, template.as_deref()),
+        AocCommand::Watch { .. } => {
+            if flags.example {
+                ensure_example_input(&day);
+            }
+            watch::watch_day(&day, flags.example);
         }
-        AocCommand::New { .. } => {
-            let status = Command::new("cargo")
-                .args(["new", &day, "--vcs", "none"])
-                .status()
-                .expect("Failed to run cargo new");
-
-            if !status.success() {
-                std::process::exit(status.code().unwrap_or(1));
+        AocCommand::Check { cases, .. } => check_day(&day, cases),
+        AocCommand::Fuzz { max_time, .. } => fuzz::fuzz_day(&day, max_time),
+        AocCommand::Step { part, .. } => {
+            if flags.example {
+                ensure_example_input(&day);
             }
-
-            let cargo_path = format!("{day}/Cargo.toml");
-            let mut cargo_toml =
-                std::fs::read_to_string(&cargo_path).expect("Failed to read Cargo.toml");
-            cargo_toml.push_str("aoc = { path = \"../common\" }\n");
-            std::fs::write(&cargo_path, cargo_toml).expect("Failed to write Cargo.toml");
-
-            let main_rs = format!("use aoc::*;\n\naoc_day!({day_num});\n");
-            std::fs::write(format!("{day}/src/main.rs"), main_rs).expect("Failed to write main.rs");
-
-            println!("Created {day}");
+            step::step_day(&day, part, flags.example);
         }
+        AocCommand::Read { .. } => read::read_day(&day),
+        AocCommand::Input { .. } => unreachable!(),
     }
 }
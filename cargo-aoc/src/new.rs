@@ -0,0 +1,302 @@
+use crate::Day;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use toml_edit::{DocumentMut, InlineTable, Item, table};
+
+const TEMPLATE_DIR: &str = ".aoc/templates";
+const DEFAULT_TEMPLATE: &str = "default";
+
+/// The binary just runs the library, which is where the solution lives so `aoc-all` can link it
+const MAIN_TEMPLATE: &str = "fn main() -> aoc::Result<()> {
+    {{crate}}::main()
+}
+";
+
+/// Used when the workspace has no `default` template of its own
+const LIB_TEMPLATE: &str = r#"use aoc::*;
+
+#[parse(lines)]
+fn parse_input(input: Lines) -> Vec<String> {
+    input.map(str::to_string).collect()
+}
+
+#[part_one]
+fn solve_part_one(lines: &[String]) -> usize {
+    lines.len()
+}
+
+#[part_two]
+fn solve_part_two(lines: &[String]) -> usize {
+    lines.len()
+}
+
+aoc_day!(year = {{year}}, day = {{day}});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Paste the example input from the puzzle page
+    const EXAMPLE: &str = "";
+
+    #[test]
+    fn part_one_example() {
+        assert_eq!(Solution::part_one(&Solution::parse(EXAMPLE)), 0);
+    }
+
+    #[test]
+    fn part_two_example() {
+        assert_eq!(Solution::part_two(&Solution::parse(EXAMPLE)), 0);
+    }
+}
+"#;
+
+/// Fills in the `{{day}}`, `{{day_padded}}`, `{{year}}` and `{{crate}}` placeholders.
+pub fn render(template: &str, day: &Day) -> String {
+    template
+        .replace("{{crate}}", &day.package.replace('-', "_"))
+        .replace("{{day_padded}}", &format!("{:02}", day.num))
+        .replace("{{day}}", &day.num.to_string())
+        .replace("{{year}}", &day.year.to_string())
+}
+
+/// Lists every file under a template directory, relative to the template root.
+fn template_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in std::fs::read_dir(dir).expect("Failed to read template directory") {
+        let path = entry.expect("Failed to read template entry").path();
+        if path.is_dir() {
+            template_files(root, &path, files);
+        } else {
+            files.push(path.strip_prefix(root).unwrap().to_path_buf());
+        }
+    }
+}
+
+fn copy_template(template: &Path, day: &Day) {
+    let mut files = vec![];
+    template_files(template, template, &mut files);
+
+    for file in files {
+        let contents = std::fs::read_to_string(template.join(&file))
+            .unwrap_or_else(|e| panic!("Failed to read template file {}: {e}", file.display()));
+        let target = Path::new(&day.path).join(&file);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).expect("Failed to create template directory");
+        }
+        std::fs::write(&target, render(&contents, day))
+            .unwrap_or_else(|e| panic!("Failed to write {}: {e}", target.display()));
+    }
+}
+
+/// Adds the `aoc` dependency unless the template already brought its own.
+fn add_aoc_dependency(day: &Day) {
+    let cargo_path = format!("{}/Cargo.toml", day.path);
+    let manifest = std::fs::read_to_string(&cargo_path).expect("Failed to read Cargo.toml");
+    let mut doc: DocumentMut = manifest.parse().expect("Failed to parse Cargo.toml");
+
+    let dependencies = doc.entry("dependencies").or_insert(table());
+    if dependencies.get("aoc").is_some() {
+        return;
+    }
+
+    let mut aoc = InlineTable::new();
+    // Days from other years are nested one directory deeper
+    let common_path = "../".repeat(Path::new(&day.path).components().count()) + "common";
+    aoc.insert("path", common_path.into());
+    dependencies["aoc"] = Item::Value(aoc.into());
+
+    std::fs::write(&cargo_path, doc.to_string()).expect("Failed to write Cargo.toml");
+}
+
+/// Links the day into the all-days binary. Only days from the configured year are linked, since
+/// days are looked up by number.
+fn add_all_dependency(day: &Day) {
+    let cargo_path = "aoc-all/Cargo.toml";
+    if day.year != crate::configured_year() || !Path::new(cargo_path).exists() {
+        return;
+    }
+
+    let manifest = std::fs::read_to_string(cargo_path).expect("Failed to read aoc-all Cargo.toml");
+    let mut doc: DocumentMut = manifest
+        .parse()
+        .expect("Failed to parse aoc-all Cargo.toml");
+
+    let mut dependency = InlineTable::new();
+    dependency.insert("path", format!("../{}", day.path).into());
+    doc.entry("dependencies").or_insert(table())[&day.package] = Item::Value(dependency.into());
+
+    std::fs::write(cargo_path, doc.to_string()).expect("Failed to write aoc-all Cargo.toml");
+}
+
+fn is_day_crate(member: &str) -> bool {
+    member.starts_with("day") || member.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Adds the day to the workspace members, keeping the day crates sorted and the existing formatting.
+fn add_workspace_member(day: &str) {
+    let manifest =
+        std::fs::read_to_string("Cargo.toml").expect("Failed to read workspace Cargo.toml");
+    std::fs::write("Cargo.toml", with_workspace_member(&manifest, day))
+        .expect("Failed to write workspace Cargo.toml");
+}
+
+fn with_workspace_member(manifest: &str, day: &str) -> String {
+    let mut doc: DocumentMut = manifest
+        .parse()
+        .expect("Failed to parse workspace Cargo.toml");
+    let members = doc["workspace"]["members"]
+        .as_array_mut()
+        .expect("workspace.members should be an array");
+
+    // Newer versions of cargo new append the member themselves, so move it into place instead
+    members.retain(|member| member.as_str() != Some(day));
+
+    let position = members
+        .iter()
+        .position(|member| {
+            member
+                .as_str()
+                .is_some_and(|member| is_day_crate(member) && member > day)
+        })
+        .unwrap_or(members.len());
+
+    // Borrow the decor of a neighbour so multi-line arrays stay multi-line. The first member's
+    // decor differs from the rest (no space after the bracket), so it moves to the new one.
+    let decor = members
+        .get(position)
+        .or_else(|| members.get(position.wrapping_sub(1)))
+        .map(|neighbour| neighbour.decor().clone());
+    let second_decor = members.get(1).map(|member| member.decor().clone());
+    members.insert(position, day);
+    if let Some(decor) = decor {
+        *members.get_mut(position).unwrap().decor_mut() = decor;
+    }
+    if position == 0
+        && let Some(old_first) = members.get_mut(1)
+    {
+        *old_first.decor_mut() = second_decor.unwrap_or_default();
+    }
+
+    doc.to_string()
+}
+
+pub fn new_day(day: &Day, template: Option<&str>) {
+    let template = match template {
+        Some(name) => {
+            let path = Path::new(TEMPLATE_DIR).join(name);
+            if !path.is_dir() {
+                eprintln!("No template named '{name}' found in {TEMPLATE_DIR}");
+                std::process::exit(1);
+            }
+            Some(path)
+        }
+        None => Some(Path::new(TEMPLATE_DIR).join(DEFAULT_TEMPLATE)).filter(|path| path.is_dir()),
+    };
+
+    let status = Command::new("cargo")
+        .args(["new", &day.path, "--name", &day.package, "--vcs", "none"])
+        .status()
+        .expect("Failed to run cargo new");
+
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+
+    // Templates can bring their own main.rs, so write the default one first
+    std::fs::write(
+        format!("{}/src/main.rs", day.path),
+        render(MAIN_TEMPLATE, day),
+    )
+    .expect("Failed to write main.rs");
+
+    match template {
+        Some(template) => copy_template(&template, day),
+        None => std::fs::write(
+            format!("{}/src/lib.rs", day.path),
+            render(LIB_TEMPLATE, day),
+        )
+        .expect("Failed to write lib.rs"),
+    }
+
+    add_aoc_dependency(day);
+    add_workspace_member(&day.path);
+    add_all_dependency(day);
+
+    println!("Created {}", day.package);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::with_workspace_member;
+
+    #[test]
+    fn single_line_members() {
+        let manifest = "[workspace]\nmembers = [\"common\", \"day01\", \"day03\"]\n";
+        assert_eq!(
+            with_workspace_member(manifest, "day02"),
+            "[workspace]\nmembers = [\"common\", \"day01\", \"day02\", \"day03\"]\n"
+        );
+    }
+
+    #[test]
+    fn multi_line_members() {
+        let manifest =
+            "[workspace]\nmembers = [\n    \"common\",\n    \"day01\",\n    \"day03\",\n]\n";
+        assert_eq!(
+            with_workspace_member(manifest, "day02"),
+            "[workspace]\nmembers = [\n    \"common\",\n    \"day01\",\n    \"day02\",\n    \"day03\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn inserts_at_the_start() {
+        let manifest = "[workspace]\nmembers = [\"day02\", \"day03\"]\n";
+        assert_eq!(
+            with_workspace_member(manifest, "day01"),
+            "[workspace]\nmembers = [\"day01\", \"day02\", \"day03\"]\n"
+        );
+
+        let manifest = "[workspace]\nmembers = [\"day02\"]\n";
+        assert_eq!(
+            with_workspace_member(manifest, "day01"),
+            "[workspace]\nmembers = [\"day01\", \"day02\"]\n"
+        );
+
+        let manifest = "[workspace]\nmembers = [\n    \"day02\",\n    \"day03\",\n]\n";
+        assert_eq!(
+            with_workspace_member(manifest, "day01"),
+            "[workspace]\nmembers = [\n    \"day01\",\n    \"day02\",\n    \"day03\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn inserts_at_the_end() {
+        let manifest = "[workspace]\nmembers = [\"common\", \"day01\"]\n";
+        assert_eq!(
+            with_workspace_member(manifest, "day02"),
+            "[workspace]\nmembers = [\"common\", \"day01\", \"day02\"]\n"
+        );
+
+        let manifest = "[workspace]\nmembers = [\n    \"common\",\n    \"day01\",\n]\n";
+        assert_eq!(
+            with_workspace_member(manifest, "day02"),
+            "[workspace]\nmembers = [\n    \"common\",\n    \"day01\",\n    \"day02\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn moves_the_member_cargo_new_appended() {
+        let manifest = "[workspace]\nmembers = [\"common\", \"day01\", \"day03\", \"day02\"]\n";
+        assert_eq!(
+            with_workspace_member(manifest, "day02"),
+            "[workspace]\nmembers = [\"common\", \"day01\", \"day02\", \"day03\"]\n"
+        );
+
+        let manifest =
+            "[workspace]\nmembers = [\n    \"day01\",\n    \"day03\",\n    \"day02\",\n]\n";
+        assert_eq!(
+            with_workspace_member(manifest, "day02"),
+            "[workspace]\nmembers = [\n    \"day01\",\n    \"day02\",\n    \"day03\",\n]\n"
+        );
+    }
+}
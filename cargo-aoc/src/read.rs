@@ -0,0 +1,67 @@
+use crate::Day;
+use scraper::{Html, Selector};
+use std::path::Path;
+
+/// The part two article only appears once part one has been accepted
+const PART_TWO_HEADING: &str = "Part Two";
+
+fn fetch_description(day: &Day) -> Result<String, String> {
+    let session =
+        std::fs::read_to_string(".session").map_err(|e| format!("Failed to read .session: {e}"))?;
+
+    let (status, html) = aoc::__request(&format!("/{}/day/{}", day.year, day.num), session.trim())
+        .map_err(|e| format!("Failed to fetch puzzle: {e}"))?;
+    if !status.is_success() {
+        return Err(format!("Failed to fetch puzzle: server returned {status}"));
+    }
+
+    let document = Html::parse_document(&html);
+    let selector = Selector::parse("article.day-desc").unwrap();
+
+    let articles = document
+        .select(&selector)
+        .map(|article| {
+            htmd::convert(&article.html()).map_err(|e| format!("Failed to convert puzzle: {e}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if articles.is_empty() {
+        return Err(format!("No puzzle description found for day {}", day.num));
+    }
+
+    Ok(articles.join("\n\n") + "\n")
+}
+
+pub fn read_day(day: &Day) {
+    if !Path::new(&day.path).is_dir() {
+        eprintln!(
+            "{} doesn't exist yet, create it with `cargo aoc new {} --year {}`",
+            day.path, day.num, day.year
+        );
+        std::process::exit(1);
+    }
+
+    let readme_path = Path::new(&day.path).join("README.md");
+    let cached = std::fs::read_to_string(&readme_path).ok();
+
+    // Keep refetching until part two shows up, after that the description never changes
+    let description = match cached {
+        Some(cached) if cached.contains(PART_TWO_HEADING) => cached,
+        cached => match fetch_description(day) {
+            Ok(description) => {
+                std::fs::write(&readme_path, &description).expect("Failed to write README.md");
+                description
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                let Some(cached) = cached else {
+                    std::process::exit(1);
+                };
+                eprintln!("Showing cached description instead");
+                cached
+            }
+        },
+    };
+
+    println!("{description}");
+}
@@ -0,0 +1,296 @@
+use crate::Day;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Widget};
+use ratatui::{DefaultTerminal, Frame as TerminalFrame};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+/// One frame recorded with `aoc::viz`, read back from a `.frames` file.
+struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<[u8; 3]>,
+    counters: Vec<(String, String)>,
+}
+
+/// Reads the format written by `aoc::viz`: `frame WIDTH HEIGHT COUNTERS`, then a `name\tvalue`
+/// line per counter and a line of `rrggbb` hex per row.
+fn parse_frames(file: &str) -> Option<Vec<Frame>> {
+    let mut lines = file.lines();
+    let mut frames = vec![];
+
+    while let Some(header) = lines.next() {
+        let [width, height, counters] = header
+            .strip_prefix("frame ")?
+            .split(' ')
+            .map(|n| n.parse::<usize>().ok())
+            .collect::<Option<Vec<_>>>()?
+            .try_into()
+            .ok()?;
+
+        let counters = (0..counters)
+            .map(|_| {
+                let (name, value) = lines.next()?.split_once('\t')?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect::<Option<_>>()?;
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for _ in 0..height {
+            let row = hex::decode(lines.next()?).ok()?;
+            pixels.extend(row.chunks_exact(3).map(|rgb| [rgb[0], rgb[1], rgb[2]]));
+        }
+        if pixels.len() != width * height {
+            return None;
+        }
+
+        frames.push(Frame {
+            width,
+            height,
+            pixels,
+            counters,
+        });
+    }
+    Some(frames)
+}
+
+/// Draws two rows of cells per line of the terminal with half blocks, cut off at the edges.
+struct GridView<'a>(&'a Frame);
+
+impl Widget for GridView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let frame = self.0;
+        let pixel = |x: usize, y: usize| {
+            (y < frame.height).then(|| {
+                let [r, g, b] = frame.pixels[y * frame.width + x];
+                Color::Rgb(r, g, b)
+            })
+        };
+
+        for row in 0..area.height.min(frame.height.div_ceil(2) as u16) {
+            for col in 0..area.width.min(frame.width as u16) {
+                let (x, y) = (col as usize, row as usize * 2);
+                let cell = &mut buf[(area.x + col, area.y + row)];
+                cell.set_symbol("▀").set_fg(pixel(x, y).unwrap());
+                if let Some(bottom) = pixel(x, y + 1) {
+                    cell.set_bg(bottom);
+                }
+            }
+        }
+    }
+}
+
+struct Stepper {
+    title: String,
+    frames: Vec<Frame>,
+    index: usize,
+    playing: bool,
+    delay: Duration,
+    /// Digits typed after `g`, until Enter
+    jump: Option<String>,
+}
+
+impl Stepper {
+    fn draw(&self, terminal_frame: &mut TerminalFrame) {
+        let [grid_area, panel_area] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(30)])
+                .areas(terminal_frame.area());
+
+        let frame = &self.frames[self.index];
+        terminal_frame.render_widget(GridView(frame), grid_area);
+
+        let mut lines = vec![
+            Line::from(format!("Frame {} of {}", self.index + 1, self.frames.len())),
+            Line::from(match self.playing {
+                true => format!("Playing every {:?}", self.delay),
+                false => "Paused".to_string(),
+            }),
+            Line::from(""),
+        ];
+        let width = frame
+            .counters
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        lines.extend(frame.counters.iter().map(|(name, value)| {
+            Line::from(vec![
+                format!("{name:<width$} ").cyan(),
+                value.clone().bold(),
+            ])
+        }));
+        lines.push(Line::from(""));
+
+        match &self.jump {
+            Some(digits) => lines.push(Line::from(format!("Jump to: {digits}_")).yellow()),
+            None => lines.extend(
+                [
+                    "←/→       previous/next",
+                    "home/end  first/last",
+                    "space     play/pause",
+                    "+/-       faster/slower",
+                    "g         jump to a frame",
+                    "q         quit",
+                ]
+                .map(|help| Line::from(help).dim()),
+            ),
+        }
+
+        let panel = Paragraph::new(lines).block(Block::bordered().title(self.title.as_str()));
+        terminal_frame.render_widget(panel, panel_area);
+    }
+
+    /// Handles a key press, returning false to quit.
+    fn key(&mut self, code: KeyCode) -> bool {
+        let last = self.frames.len() - 1;
+
+        if let Some(digits) = &mut self.jump {
+            match code {
+                KeyCode::Char(c) if c.is_ascii_digit() => digits.push(c),
+                KeyCode::Backspace => {
+                    digits.pop();
+                }
+                KeyCode::Enter => {
+                    // Frames are numbered from 1, like the panel shows them
+                    if let Ok(frame) = digits.parse::<usize>() {
+                        self.index = frame.saturating_sub(1).min(last);
+                    }
+                    self.jump = None;
+                }
+                KeyCode::Esc => self.jump = None,
+                _ => {}
+            }
+            return true;
+        }
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('n') => {
+                self.index = (self.index + 1).min(last)
+            }
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('p') => {
+                self.index = self.index.saturating_sub(1)
+            }
+            KeyCode::Home => self.index = 0,
+            KeyCode::End => self.index = last,
+            KeyCode::Char(' ') => {
+                // Playing from the end starts again from the beginning
+                if !self.playing && self.index == last {
+                    self.index = 0;
+                }
+                self.playing = !self.playing;
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.delay = (self.delay / 2).max(Duration::from_millis(10))
+            }
+            KeyCode::Char('-') => self.delay = (self.delay * 2).min(Duration::from_secs(5)),
+            KeyCode::Char('g') => {
+                self.playing = false;
+                self.jump = Some(String::new());
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> std::io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let timeout = match self.playing {
+                true => self.delay,
+                false => Duration::from_secs(60),
+            };
+            if event::poll(timeout)? {
+                if let Event::Key(key) = event::read()?
+                    && key.kind == KeyEventKind::Press
+                    && !self.key(key.code)
+                {
+                    return Ok(());
+                }
+            } else if self.playing {
+                self.index = (self.index + 1).min(self.frames.len() - 1);
+                self.playing = self.index < self.frames.len() - 1;
+            }
+        }
+    }
+}
+
+/// The `.frames` file to step through: the given part's, or the first one recorded.
+fn frames_path(dir: &Path, part: Option<u32>) -> Option<PathBuf> {
+    if let Some(part) = part {
+        let path = dir.join(format!("part{part}.frames"));
+        return path.exists().then_some(path);
+    }
+
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "frames"))
+        .collect();
+    // parse, part1, part1-alt, part2
+    paths.sort();
+    paths.into_iter().next()
+}
+
+/// Runs the day with `--viz`, then steps through the frames one of its parts recorded.
+pub fn step_day(day: &Day, part: Option<u32>, example: bool) {
+    let mut args = vec![
+        "run",
+        "--release",
+        "-q",
+        "-p",
+        &day.package,
+        "--features",
+        "aoc/viz",
+        "--",
+        "--viz",
+    ];
+    if example {
+        args.push("--example");
+    }
+
+    // A part that panics still saves its frames, which is often what's worth looking at
+    let status = Command::new("cargo")
+        .args(&args)
+        .status()
+        .expect("Failed to run cargo");
+
+    let dir = Path::new(".viz").join(format!("day{:02}", day.num));
+    let Some(path) = frames_path(&dir, part) else {
+        match part {
+            Some(part) => eprintln!("Part {part} didn't record any frames with aoc::viz"),
+            None => eprintln!("No part recorded any frames with aoc::viz"),
+        }
+        std::process::exit(status.code().filter(|&code| code != 0).unwrap_or(1));
+    };
+
+    let file = std::fs::read_to_string(&path).expect("Failed to read frames");
+    let frames = match parse_frames(&file) {
+        Some(frames) if !frames.is_empty() => frames,
+        _ => {
+            eprintln!("{} isn't a valid frames file", path.display());
+            std::process::exit(1);
+        }
+    };
+
+    let name = path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut stepper = Stepper {
+        title: format!(" Day {} {name} ", day.num),
+        frames,
+        index: 0,
+        playing: false,
+        delay: Duration::from_millis(200),
+        jump: None,
+    };
+
+    let mut terminal = ratatui::init();
+    let result = stepper.run(&mut terminal);
+    ratatui::restore();
+    result.expect("Failed to draw the stepper");
+}
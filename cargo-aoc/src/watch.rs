@@ -0,0 +1,232 @@
+use crate::Day;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+/// Editors often write a file several times in a row, so wait for things to settle before re-running
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Set while the day is running, when Ctrl-C is left for it to report the parts that finished
+static RUNNING: AtomicBool = AtomicBool::new(false);
+/// Ctrl-C was pressed while the day was running, so stop once its output is shown
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+const BOLD_YELLOW: &str = "\x1b[1;93m";
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// One line of the runner's `--time` output, e.g. `Part 1: 42 (1.23ms)` or `Parse (200.00µs)`.
+struct Step {
+    name: String,
+    answer: Option<String>,
+    time: Option<Duration>,
+}
+
+/// Parses Rust's `Debug` formatting of a `Duration`, e.g. `1.23ms`.
+fn parse_time(time: &str) -> Option<Duration> {
+    let split = time.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = time.split_at(split);
+    let value: f64 = value.parse().ok()?;
+
+    let nanos = match unit {
+        "ns" => value,
+        "µs" => value * 1e3,
+        "ms" => value * 1e6,
+        "s" => value * 1e9,
+        _ => return None,
+    };
+    Some(Duration::from_nanos(nanos as u64))
+}
+
+fn parse_step(line: &str) -> Option<Step> {
+    let (head, time) = match line.rsplit_once(" (") {
+        Some((head, time)) if time.ends_with(')') => (head, parse_time(time.trim_end_matches(')'))),
+        _ => (line, None),
+    };
+
+    if head == "Parse" {
+        return Some(Step {
+            name: head.to_string(),
+            answer: None,
+            time,
+        });
+    }
+
+    let (name, answer) = head.split_once(": ")?;
+    name.starts_with("Part ").then(|| Step {
+        name: name.to_string(),
+        answer: Some(answer.to_string()),
+        time,
+    })
+}
+
+/// Builds and runs the day, returning the steps it reported and any other output.
+fn run(day: &Day, example: bool) -> (Vec<Step>, Vec<String>) {
+    let mut args = vec!["run", "--release", "-q", "-p", &day.package, "--", "--time"];
+    if example {
+        args.push("--example");
+    }
+
+    // Compile errors and panics go straight to the terminal
+    RUNNING.store(true, Ordering::SeqCst);
+    let output = Command::new("cargo")
+        .args(&args)
+        .stderr(Stdio::inherit())
+        .output()
+        .expect("Failed to run cargo");
+    RUNNING.store(false, Ordering::SeqCst);
+
+    let mut steps = vec![];
+    let mut other = vec![];
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        match parse_step(line) {
+            Some(step) => steps.push(step),
+            None => other.push(line.to_string()),
+        }
+    }
+    (steps, other)
+}
+
+fn render(steps: &[Step], previous: &[Step]) {
+    let width = steps
+        .iter()
+        .filter_map(|step| step.answer.as_ref())
+        .map(|answer| answer.len())
+        .max()
+        .unwrap_or(0);
+
+    for step in steps {
+        let before = previous.iter().find(|before| before.name == step.name);
+
+        let answer = match (
+            &step.answer,
+            before.and_then(|before| before.answer.as_ref()),
+        ) {
+            (Some(answer), Some(old)) if answer != old => {
+                format!(": {BOLD_YELLOW}{answer:<width$}{RESET}")
+            }
+            (Some(answer), _) => format!(": {answer:<width$}"),
+            (None, _) => format!("  {:width$}", ""),
+        };
+
+        let time = match (step.time, before.and_then(|before| before.time)) {
+            (Some(time), Some(old)) => {
+                let change = time.as_secs_f64() / old.as_secs_f64() - 1.0;
+                let colour = if change > 0.0 { RED } else { GREEN };
+                format!("{time:>10.2?} {colour}{:+6.1}%{RESET}", change * 100.0)
+            }
+            (Some(time), None) => format!("{time:>10.2?}"),
+            (None, _) => String::new(),
+        };
+
+        let was = match (
+            &step.answer,
+            before.and_then(|before| before.answer.as_ref()),
+        ) {
+            (Some(answer), Some(old)) if answer != old => format!("  {DIM}(was {old}){RESET}"),
+            _ => String::new(),
+        };
+
+        println!("{:<6}{answer}  {time}{was}", step.name);
+    }
+}
+
+/// Whether a changed file should trigger a re-run. Other days' inputs live in the same directory.
+fn is_relevant(path: &Path, day: &Day, input_dir: &Path) -> bool {
+    if !path.starts_with(input_dir) {
+        return true;
+    }
+
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let input = format!("day{}", day.num);
+    name == input
+        || name.starts_with(&format!("{input}_"))
+        || name.starts_with(&format!("{input}."))
+}
+
+fn wait_for_change(events: &Receiver<notify::Result<Event>>, day: &Day, input_dir: &Path) {
+    loop {
+        let Ok(event) = events.recv() else {
+            eprintln!("File watcher stopped");
+            std::process::exit(1);
+        };
+        let Ok(event) = event else {
+            continue;
+        };
+
+        let is_write = matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        );
+        if is_write
+            && event
+                .paths
+                .iter()
+                .any(|path| is_relevant(path, day, input_dir))
+        {
+            break;
+        }
+    }
+
+    while events.recv_timeout(DEBOUNCE).is_ok() {}
+}
+
+pub fn watch_day(day: &Day, example: bool) -> ! {
+    let cwd = std::env::current_dir().expect("Failed to get current directory");
+    let input_dir = cwd.join(".input").join(day.year.to_string());
+    std::fs::create_dir_all(&input_dir).expect("Failed to create .input directory");
+
+    let (sender, events) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).expect("Failed to create file watcher");
+    for path in [
+        cwd.join(&day.path).join("src"),
+        cwd.join("common/src"),
+        cwd.join("common/aoc_macro/src"),
+    ] {
+        watcher
+            .watch(&path, RecursiveMode::Recursive)
+            .unwrap_or_else(|e| panic!("Failed to watch {}: {e}", path.display()));
+    }
+    watcher
+        .watch(&input_dir, RecursiveMode::NonRecursive)
+        .expect("Failed to watch .input");
+
+    // Stops watching, unless the day is running and gets to report what finished first
+    let _ = ctrlc::set_handler(|| match RUNNING.load(Ordering::SeqCst) {
+        true => INTERRUPTED.store(true, Ordering::SeqCst),
+        false => std::process::exit(130),
+    });
+
+    let mut previous = vec![];
+    for run_number in 1.. {
+        // Clear the screen and move to the top
+        print!("\x1b[2J\x1b[H");
+        println!(
+            "Watching {}{} (run {run_number}), Ctrl-C to stop\n",
+            day.package,
+            if example { " with example input" } else { "" }
+        );
+
+        let (steps, other) = run(day, example);
+        for line in other {
+            println!("{line}");
+        }
+        render(&steps, &previous);
+        if INTERRUPTED.load(Ordering::SeqCst) {
+            std::process::exit(130);
+        }
+        if !steps.is_empty() {
+            previous = steps;
+        }
+
+        // Ignore anything the run itself touched, like downloading the input
+        while events.try_recv().is_ok() {}
+        wait_for_change(&events, day, &input_dir);
+    }
+    unreachable!()
+}
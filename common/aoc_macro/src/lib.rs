@@ -3,6 +3,7 @@ use proc_macro2::{Literal, Span};
 use quote::quote;
 use syn::Ident;
 use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
 use syn::{ItemFn, parse_macro_input};
 
 enum ReturnType {
@@ -44,6 +45,27 @@ pub(crate) fn get_return_type(ty: &syn::Type) -> ReturnType {
     crate::ReturnType::Plain(quote! { #ty })
 }
 
+/// Ties every lifetime in the parsed type to the input, so the type can be named as
+/// `__ParsedData<'__input>`. Covers the parse function's own lifetimes, `'_` and elided references.
+struct InputLifetime {
+    names: Vec<Ident>,
+}
+
+impl VisitMut for InputLifetime {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        if lifetime.ident == "_" || self.names.contains(&lifetime.ident) {
+            *lifetime = syn::Lifetime::new("'__input", Span::call_site());
+        }
+    }
+
+    fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = Some(syn::Lifetime::new("'__input", Span::call_site()));
+        }
+        syn::visit_mut::visit_type_reference_mut(self, reference);
+    }
+}
+
 fn get_parse_type(attr: TokenStream) -> Result<ParseType, syn::Error> {
     if attr.is_empty() {
         return Ok(ParseType::Text);
@@ -65,6 +87,43 @@ fn get_parse_type(attr: TokenStream) -> Result<ParseType, syn::Error> {
     }
 }
 
+/// Options for `#[part_one(...)]` and `#[part_two(...)]`
+#[derive(Default)]
+struct PartOptions {
+    /// Name of an alternative implementation, cross-checked against the main one
+    alt: Option<syn::LitStr>,
+    /// Part two takes part one's result as its last argument
+    after_part_one: bool,
+    /// A slow reference implementation, only run by `cargo aoc check`
+    oracle: bool,
+}
+
+fn get_part_options(attr: TokenStream) -> Result<PartOptions, syn::Error> {
+    let mut options = PartOptions::default();
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("alt") {
+            options.alt = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("after_part_one") {
+            options.after_part_one = true;
+            Ok(())
+        } else if meta.path.is_ident("oracle") {
+            options.oracle = true;
+            Ok(())
+        } else {
+            Err(meta.error(
+                "invalid part option, expected `alt = \"name\"`, `after_part_one` or `oracle`",
+            ))
+        }
+    });
+    syn::parse::Parser::parse(parser, attr)?;
+    // Oracles are alternatives that only get run against generated inputs
+    if options.oracle && options.alt.is_none() {
+        options.alt = Some(syn::LitStr::new("oracle", Span::call_site()));
+    }
+    Ok(options)
+}
+
 fn create_parse_call(
     fn_name: &Ident,
     parse_type: &ParseType,
@@ -73,10 +132,10 @@ fn create_parse_call(
     match parse_type {
         ParseType::Line => match return_type {
             ReturnType::Plain(_) => {
-                quote! { text.lines().map(|line: &str| #fn_name(line)).collect::<Vec<_>>() }
+                quote! { text.lines().map(|line| #fn_name(line)).collect::<Vec<_>>() }
             }
             ReturnType::Result(_) => {
-                quote! { text.lines().map(|line: &str| #fn_name(line)).collect::<Result<Vec<_>, _>>() }
+                quote! { text.lines().map(|line| #fn_name(line)).collect::<Result<Vec<_>, _>>() }
             }
         },
         ParseType::Lines => quote! { #fn_name(text.lines()) },
@@ -103,7 +162,19 @@ pub fn parse(attr: TokenStream, item: TokenStream) -> TokenStream {
                 .to_compile_error()
                 .into();
         }
-        syn::ReturnType::Type(_, ty) => get_return_type(ty),
+        syn::ReturnType::Type(_, ty) => {
+            let mut ty = (**ty).clone();
+            InputLifetime {
+                names: input
+                    .sig
+                    .generics
+                    .lifetimes()
+                    .map(|param| param.lifetime.ident.clone())
+                    .collect(),
+            }
+            .visit_type_mut(&mut ty);
+            get_return_type(&ty)
+        }
     };
 
     let parse_call = create_parse_call(fn_name, &parse_type, &return_type);
@@ -121,58 +192,183 @@ pub fn parse(attr: TokenStream, item: TokenStream) -> TokenStream {
         ReturnType::Result(_) => quote! { #parse_call.expect("Unable to parse input") },
     };
 
+    // For __try_parse, an error means the input is rejected rather than a crash
+    let try_parse_body = match &return_type {
+        ReturnType::Plain(_) => quote! { Some(#parse_call) },
+        ReturnType::Result(_) => quote! { #parse_call.ok() },
+    };
+
     let expanded = quote! {
-        static __PARSED_DATA: std::sync::OnceLock<#parsed_data_type> = std::sync::OnceLock::new();
+        type __ParsedData<'__input> = #parsed_data_type;
 
         #fn_vis #fn_sig {
             #fn_block
         }
 
         /// Returns parsed data (can be called multiple times, for benchmarks)
-        fn __do_parse(text: &str) -> #parsed_data_type {
+        fn __do_parse(text: &str) -> __ParsedData<'_> {
             #do_parse_body
         }
 
-        fn __parse_data(text: &str) {
-            let data = __do_parse(text);
-            __PARSED_DATA.set(data).unwrap();
+        /// Returns None if the parse function returned an error (for fuzzing)
+        #[allow(dead_code)]
+        fn __try_parse(text: &str) -> Option<__ParsedData<'_>> {
+            #try_parse_body
+        }
+
+        inventory::submit! {
+            aoc::AocBench {
+                day: crate::__AOC_DAY,
+                part: 0,
+                name: stringify!(#fn_name),
+                alt: None,
+                kind: aoc::BenchKind::Parse,
+                func: |b, input| b.iter(|| __do_parse(std::hint::black_box(input))),
+            }
         }
     };
 
     TokenStream::from(expanded)
 }
 
-fn create_part_definition(part: u32, item: TokenStream) -> TokenStream {
+/// How a part function takes the parsed data (or one element of it)
+#[derive(Clone, Copy, PartialEq)]
+enum ArgKind {
+    Ref,
+    Mut,
+    Owned,
+}
+
+fn get_arg_kind(arg: &syn::FnArg) -> ArgKind {
+    match arg {
+        syn::FnArg::Typed(arg) => match &*arg.ty {
+            syn::Type::Reference(reference) if reference.mutability.is_some() => ArgKind::Mut,
+            syn::Type::Reference(_) => ArgKind::Ref,
+            _ => ArgKind::Owned,
+        },
+        syn::FnArg::Receiver(_) => ArgKind::Ref,
+    }
+}
+
+fn create_part_definition(part: u32, attr: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as ItemFn);
+    let options = match get_part_options(attr) {
+        Ok(options) => options,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     let fn_name = &input.sig.ident;
     let fn_vis = &input.vis;
     let fn_sig = &input.sig;
     let fn_block = &input.block;
 
-    let wrapper_name = match part {
-        1 => Ident::new("__part_one", Span::call_site()),
-        2 => Ident::new("__part_two", Span::call_site()),
+    let part_name = match part {
+        1 => "part_one",
+        2 => "part_two",
         _ => panic!("Invalid part number"),
     };
 
-    let bench_name = match part {
-        1 => Ident::new("__bench_part_one", Span::call_site()),
-        2 => Ident::new("__bench_part_two", Span::call_site()),
-        _ => panic!("Invalid part number"),
+    // Alternative implementations get their own wrappers, named after the function
+    let suffix = match &options.alt {
+        Some(_) => format!("{part_name}_{fn_name}"),
+        None => part_name.to_string(),
     };
+    let wrapper_name = Ident::new(&format!("__{suffix}"), Span::call_site());
+    let bench_name = Ident::new(&format!("__bench_{suffix}"), Span::call_site());
+
+    let alt = match &options.alt {
+        Some(alt) => quote! { Some(#alt) },
+        None => quote! { None },
+    };
+    let after_part_one = options.after_part_one;
+    let oracle = options.oracle;
 
     let part_literal = Literal::u32_unsuffixed(part);
 
-    // Destructure tuples into individual function args
-    // In both cases, `data` is a reference (wrapper: from get(), bench: we bind as &)
-    let param_count = input.sig.inputs.len();
+    if options.after_part_one && part != 2 {
+        return syn::Error::new(fn_name.span(), "only part two can run after part one")
+            .to_compile_error()
+            .into();
+    }
+
+    // Part one's result is passed as the last argument, after the parsed data
+    let part_one_type = if options.after_part_one {
+        let reference = match input.sig.inputs.last() {
+            Some(syn::FnArg::Typed(arg)) => match &*arg.ty {
+                syn::Type::Reference(reference) => Some(reference.elem.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        if reference.is_none() {
+            return syn::Error::new(
+                input.sig.inputs.span(),
+                "after_part_one needs part one's result as a reference in the last argument",
+            )
+            .to_compile_error()
+            .into();
+        }
+        reference
+    } else {
+        None
+    };
 
-    let fn_call = if param_count <= 1 {
-        quote! { #fn_name(data) }
+    // Destructure tuples into individual function args
+    let param_count = input.sig.inputs.len() - usize::from(options.after_part_one);
+    let arg_kinds: Vec<_> = input
+        .sig
+        .inputs
+        .iter()
+        .take(param_count)
+        .map(get_arg_kind)
+        .collect();
+
+    // Parts that take their data by value or `&mut` are handed a copy of their own, and the
+    // value function takes the whole parsed data the same way
+    let data_kind = if arg_kinds.contains(&ArgKind::Owned) {
+        ArgKind::Owned
+    } else if arg_kinds.contains(&ArgKind::Mut) {
+        ArgKind::Mut
     } else {
-        let indices: Vec<_> = (0..param_count).map(syn::Index::from).collect();
-        quote! { #fn_name(#(&data.#indices),*) }
+        ArgKind::Ref
+    };
+    let data_args = if param_count <= 1 {
+        quote! { data }
+    } else {
+        let args = arg_kinds.iter().enumerate().map(|(i, kind)| {
+            let index = syn::Index::from(i);
+            match kind {
+                ArgKind::Ref => quote! { &data.#index },
+                ArgKind::Mut => quote! { &mut data.#index },
+                ArgKind::Owned => quote! { data.#index },
+            }
+        });
+        quote! { #(#args),* }
+    };
+    let data_mut = match arg_kinds.contains(&ArgKind::Mut) {
+        true => quote! { mut },
+        false => quote! {},
+    };
+    let (data_param, pass_data) = match data_kind {
+        ArgKind::Ref => (quote! { data: &__ParsedData<'_> }, quote! { data }),
+        ArgKind::Mut => (quote! { data: &mut __ParsedData<'_> }, quote! { &mut data }),
+        ArgKind::Owned => (quote! { #data_mut data: __ParsedData<'_> }, quote! { data }),
+    };
+    // A clone if the parsed data is Clone, otherwise parse the input again
+    let fresh_copy = quote! {
+        {
+            use aoc::__fresh::{ViaClone as _, ViaParse as _};
+            (&aoc::__fresh::Fresh(data)).fresh_copy(|| __do_parse(input))
+        }
+    };
+    let take_copy = match data_kind {
+        ArgKind::Ref => quote! {},
+        _ => quote! { let #data_mut data = #fresh_copy; },
+    };
+
+    let (first_param, pass_first) = match &part_one_type {
+        Some(ty) => (quote! { , first: &#ty }, quote! { , first }),
+        None => (quote! {}, quote! {}),
     };
 
     // Handle Result return types
@@ -180,23 +376,138 @@ fn create_part_definition(part: u32, item: TokenStream) -> TokenStream {
         syn::ReturnType::Default => ReturnType::Plain(quote! { () }),
         syn::ReturnType::Type(_, ty) => get_return_type(ty),
     };
+    let inner_type = return_type.inner_type();
 
-    let get_result = match &return_type {
-        ReturnType::Plain(_) => quote! { let result = #fn_call; },
+    let fn_call = quote! { #fn_name(#data_args #pass_first) };
+    let unwrapped_call = match &return_type {
+        ReturnType::Plain(_) => quote! { #fn_call },
         ReturnType::Result(_) => {
             let err_msg = format!("Part {} failed", part);
-            quote! { let result = #fn_call.expect(#err_msg); }
+            quote! { #fn_call.expect(#err_msg) }
         }
     };
 
-    let bench_result = match &return_type {
-        ReturnType::Plain(_) => quote! {
-            let data = &__do_parse(input);
-            #fn_call.to_string()
+    // The main implementation is exposed as Solution::part_one/part_two, alternatives just get a
+    // function of their own
+    let (value_fn, value_definition) = match &options.alt {
+        None => {
+            let method = Ident::new(part_name, Span::call_site());
+            (
+                quote! { Solution::#method },
+                quote! {
+                    impl Solution {
+                        pub fn #method(#data_param #first_param) -> #inner_type {
+                            #unwrapped_call
+                        }
+                    }
+                },
+            )
+        }
+        Some(_) => {
+            let value_name = Ident::new(&format!("__{suffix}_value"), Span::call_site());
+            (
+                quote! { #value_name },
+                quote! {
+                    fn #value_name(#data_param #first_param) -> #inner_type {
+                        #unwrapped_call
+                    }
+                },
+            )
+        }
+    };
+
+    // Part one's full result can be kept for part two. #[part_two(after_part_one)] shadows
+    // the default __keep_part_one with one that keeps it.
+    let is_main_part_one = part == 1 && options.alt.is_none();
+    let part_one_value = if is_main_part_one {
+        quote! {
+            #[allow(dead_code)]
+            fn __part_one_value(data: &__ParsedData<'_>, input: &str) -> #inner_type {
+                #take_copy
+                #value_fn(#pass_data)
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let kept = match is_main_part_one {
+        true => quote! { __keep_part_one(result) },
+        false => quote! { None },
+    };
+
+    let (keep_part_one, get_first, bench_first) = match (&part_one_type, &options.alt) {
+        (Some(ty), None) => (
+            quote! {
+                fn __keep_part_one(result: #ty) -> Option<Box<dyn std::any::Any>> {
+                    Some(Box::new(result))
+                }
+            },
+            quote! { let first = context.part_one::<#ty>(); },
+            quote! { let first = &__part_one_value(data, input); },
+        ),
+        (Some(ty), Some(_)) => (
+            quote! {},
+            quote! { let first = context.part_one::<#ty>(); },
+            quote! { let first = &__part_one_value(data, input); },
+        ),
+        (None, _) => (quote! {}, quote! {}, quote! {}),
+    };
+
+    // Copies are made outside the timed part of the solve benchmark. The total benchmark parses
+    // the input itself, so it hands that over instead of copying.
+    let bench_solve = match data_kind {
+        ArgKind::Ref => quote! { b.iter(|| #value_fn(data #pass_first)) },
+        _ => quote! {
+            b.iter_batched(
+                || #fresh_copy,
+                |#data_mut data| #value_fn(#pass_data #pass_first),
+                aoc::criterion::BatchSize::LargeInput,
+            )
         },
-        ReturnType::Result(_) => quote! {
+    };
+    let bench_parse = match (data_kind, part_one_type.is_some()) {
+        (ArgKind::Ref, _) => quote! {
             let data = &__do_parse(input);
-            #fn_call.unwrap().to_string()
+            #bench_first
+        },
+        (_, false) => quote! { let #data_mut data = __do_parse(input); },
+        (_, true) => quote! {
+            let parsed = __do_parse(input);
+            let data = &parsed;
+            #bench_first
+            let #data_mut data = parsed;
+        },
+    };
+
+    // Oracles are too slow to be worth benchmarking
+    let benches = match options.oracle {
+        true => quote! {},
+        false => quote! {
+            inventory::submit! {
+                aoc::AocBench {
+                    day: crate::__AOC_DAY,
+                    part: #part_literal,
+                    name: stringify!(#fn_name),
+                    alt: #alt,
+                    kind: aoc::BenchKind::Solve,
+                    func: |b, input| {
+                        let data = &__do_parse(input);
+                        #bench_first
+                        #bench_solve
+                    },
+                }
+            }
+
+            inventory::submit! {
+                aoc::AocBench {
+                    day: crate::__AOC_DAY,
+                    part: #part_literal,
+                    name: stringify!(#fn_name),
+                    alt: #alt,
+                    kind: aoc::BenchKind::Total,
+                    func: |b, input| b.iter(|| #bench_name(std::hint::black_box(input))),
+                }
+            }
         },
     };
 
@@ -205,41 +516,82 @@ fn create_part_definition(part: u32, item: TokenStream) -> TokenStream {
             #fn_block
         }
 
-        fn #wrapper_name() -> String {
-            let data = __PARSED_DATA.get().unwrap();
-            #get_result
-            result.to_string()
+        #value_definition
+
+        #part_one_value
+
+        #keep_part_one
+
+        #[allow(unused_variables)]
+        fn #wrapper_name(context: &aoc::PartContext) -> aoc::PartAnswer {
+            let input = context.input();
+            // Borrowed data is stored as 'static, so shorten it back to the context's lifetime
+            let data = __shorten(context.parsed::<__ParsedData<'static>>());
+            #get_first
+            #take_copy
+            let result = #value_fn(#pass_data #pass_first);
+            aoc::PartAnswer {
+                answer: result.to_string(),
+                kept: #kept,
+            }
         }
 
         /// Benchmark entry point - takes raw input, returns result as string
         pub fn #bench_name(input: &str) -> String {
-            #bench_result
+            #bench_parse
+            #value_fn(#pass_data #pass_first).to_string()
         }
 
         inventory::submit! {
             aoc::AocPart {
+                day: crate::__AOC_DAY,
                 part: #part_literal,
+                name: stringify!(#fn_name),
+                alt: #alt,
+                after_part_one: #after_part_one,
+                oracle: #oracle,
                 func: #wrapper_name,
             }
         }
 
-        inventory::submit! {
-            aoc::AocBench {
-                part: #part_literal,
-                func: #bench_name,
-            }
-        }
+        #benches
     };
 
     TokenStream::from(expanded)
 }
 
 #[proc_macro_attribute]
-pub fn part_one(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    create_part_definition(1, item)
+pub fn part_one(attr: TokenStream, item: TokenStream) -> TokenStream {
+    create_part_definition(1, attr, item)
+}
+
+#[proc_macro_attribute]
+pub fn part_two(attr: TokenStream, item: TokenStream) -> TokenStream {
+    create_part_definition(2, attr, item)
 }
 
+/// Registers a proptest strategy for random valid inputs, used by `cargo aoc check` to compare
+/// each part against its alternatives and oracles.
 #[proc_macro_attribute]
-pub fn part_two(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    create_part_definition(2, item)
+pub fn generator(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(Span::call_site(), "#[generator] doesn't take any options")
+            .to_compile_error()
+            .into();
+    }
+    let input = parse_macro_input!(item as ItemFn);
+    let fn_name = &input.sig.ident;
+
+    let expanded = quote! {
+        #input
+
+        inventory::submit! {
+            aoc::AocGenerator {
+                day: crate::__AOC_DAY,
+                strategy: || aoc::proptest::strategy::Strategy::boxed(#fn_name()),
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
 }
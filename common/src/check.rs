@@ -0,0 +1,159 @@
+//! `--check`: runs every implementation of each part, oracles included, against random inputs from
+//! the day's #[generator]. Inputs they disagree or panic on are shrunk to the smallest proptest can
+//! find. Crashes found by `cargo aoc fuzz` are replayed first.
+
+use crate::{AocDay, AocGenerator, AocPart, Parsed, PartContext, Result, bail};
+use itertools::Itertools;
+use proptest::test_runner::{Config, TestCaseError, TestError, TestRunner};
+
+const DEFAULT_CASES: u32 = 256;
+/// Inputs longer than this are saved to a file instead of printed
+const MAX_PRINTED_LINES: usize = 30;
+
+pub(crate) fn check_day(day: &AocDay) -> Result<()> {
+    let parts = inventory::iter::<AocPart>
+        .into_iter()
+        .filter(|p| p.day == day.day)
+        .sorted_by_key(|p| (p.part, p.alt))
+        .collect_vec();
+    let generator = inventory::iter::<AocGenerator>
+        .into_iter()
+        .find(|generator| generator.day == day.day);
+    let regressions = crate::arg_value("--regressions").map(std::path::PathBuf::from);
+
+    if let Some(dir) = &regressions {
+        check_regressions(day, &parts, dir)?;
+    }
+    let Some(generator) = generator else {
+        if regressions.is_some() {
+            return Ok(());
+        }
+        bail!("Day {} has no #[generator] to make inputs with", day.day);
+    };
+
+    let cases = match crate::arg_value("--cases") {
+        Some(cases) => cases.parse().unwrap_or_else(|_| {
+            eprintln!("Invalid case count '{cases}'");
+            std::process::exit(1);
+        }),
+        None => DEFAULT_CASES,
+    };
+    let mut runner = TestRunner::new(Config {
+        cases,
+        failure_persistence: None,
+        // Big inputs with slow oracles can take a long time to shrink all the way
+        max_shrink_time: 60_000,
+        ..Config::default()
+    });
+
+    // Panics are failures to shrink like any other, so don't print each one along the way
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = runner.run(&(generator.strategy)(), |input| {
+        let parsed = catch_panic("Parsing", || Parsed::new(input.into(), day.parse))?;
+        check_input(&parsed, &parts)
+    });
+    std::panic::set_hook(hook);
+
+    match result {
+        Ok(()) => {
+            let labels = parts.iter().map(|part| part.label()).join(", ");
+            println!("{cases} inputs passed ({labels})");
+            Ok(())
+        }
+        Err(TestError::Fail(reason, input)) => {
+            if input.lines().count() <= MAX_PRINTED_LINES {
+                println!("Smallest failing input:\n{input}\n");
+            } else {
+                // Not in .input, where it would be mistaken for a cached input
+                let path = crate::workspace_root()
+                    .join("target/aoc-check")
+                    .join(format!("day{}", day.day));
+                std::fs::create_dir_all(path.parent().unwrap())?;
+                std::fs::write(&path, &input)?;
+                println!("Smallest failing input saved to {}\n", path.display());
+            }
+            println!("{reason}");
+            bail!("Check failed")
+        }
+        Err(TestError::Abort(reason)) => bail!("Check gave up: {reason}"),
+    }
+}
+
+/// Replays every input in `dir`, which `cargo aoc fuzz` saves crashes to. Inputs the #[parse]
+/// function returns an error for are fine, it's panics that count.
+fn check_regressions(day: &AocDay, parts: &[&AocPart], dir: &std::path::Path) -> Result<()> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(());
+    };
+    let paths = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .sorted()
+        .collect_vec();
+
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let mut failed = 0;
+    for path in &paths {
+        let input = String::from_utf8_lossy(&std::fs::read(path)?).into_owned();
+        let result = catch_panic("Parsing", || Parsed::try_new(input.into(), day.try_parse))
+            .and_then(|parsed| match parsed {
+                Some(parsed) => check_input(&parsed, parts),
+                None => Ok(()),
+            });
+        if let Err(reason) = result {
+            println!("{}: {reason}", path.display());
+            failed += 1;
+        }
+    }
+    std::panic::set_hook(hook);
+
+    if failed > 0 {
+        bail!("{failed} of {} regression inputs failed", paths.len());
+    }
+    if !paths.is_empty() {
+        println!("{} regression inputs passed", paths.len());
+    }
+    Ok(())
+}
+
+/// Runs every part, failing if any panic or disagree.
+fn check_input(parsed: &Parsed, parts: &[&AocPart]) -> Result<(), TestCaseError> {
+    let answers = run_parts(parsed, parts)?;
+    let disagreements = crate::disagreements(parts, &answers.iter().map(Some).collect_vec());
+    match disagreements.is_empty() {
+        true => Ok(()),
+        false => Err(TestCaseError::fail(disagreements.join("\n"))),
+    }
+}
+
+/// Runs `f`, failing with whatever it panicked with.
+fn catch_panic<T>(label: &str, f: impl FnOnce() -> T) -> Result<T, TestCaseError> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).map_err(|panic| {
+        let message = match (panic.downcast_ref::<&str>(), panic.downcast_ref::<String>()) {
+            (Some(message), _) => message,
+            (_, Some(message)) => message.as_str(),
+            _ => "unknown panic",
+        };
+        TestCaseError::fail(format!("{label} panicked: {message}"))
+    })
+}
+
+/// Runs each part on the parsed input in order, on this thread.
+fn run_parts(parsed: &Parsed, parts: &[&AocPart]) -> Result<Vec<String>, TestCaseError> {
+    let mut part_one = None;
+    let mut answers = vec![];
+    for part in parts {
+        let context = PartContext {
+            input: &parsed.input,
+            parsed: &*parsed.data,
+            part_one: part_one.as_deref(),
+        };
+        let result = catch_panic(&part.label(), || (part.func)(&context))?;
+        answers.push(result.answer);
+        if result.kept.is_some() {
+            part_one = result.kept;
+        }
+    }
+    Ok(answers)
+}
@@ -0,0 +1,102 @@
+//! Output for `debug!` and `trace!`, shown with `--example` or `--verbose`. Each part's output is
+//! collected and printed under its answer, so parts running at the same time don't interleave.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+pub const ENABLED: bool = cfg!(feature = "debug");
+
+/// Lines logged by one part (or the parser)
+pub(crate) type Log = Arc<Mutex<Vec<String>>>;
+
+/// Set while benchmarking, which can run in the same process as a debug build
+static SILENCED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Where output from this thread goes, or stderr if it isn't running a part
+    static LOG: RefCell<Option<Log>> = const { RefCell::new(None) };
+}
+
+/// Whether `debug!` and `trace!` print anything, which is never without the `debug` feature.
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED && requested() && !SILENCED.load(Ordering::Relaxed)
+}
+
+#[cfg(feature = "debug")]
+fn requested() -> bool {
+    static REQUESTED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *REQUESTED.get_or_init(|| std::env::args().any(|arg| arg == "--example" || arg == "--verbose"))
+}
+
+#[cfg(not(feature = "debug"))]
+fn requested() -> bool {
+    false
+}
+
+#[doc(hidden)]
+pub fn __log(message: std::fmt::Arguments) {
+    LOG.with_borrow(|log| match log {
+        Some(log) => log.lock().unwrap().push(message.to_string()),
+        // e.g. rayon's worker threads
+        None => eprintln!("{message}"),
+    });
+}
+
+/// Runs `f` with this thread's output going to `log`.
+pub(crate) fn capture<T>(log: &Log, f: impl FnOnce() -> T) -> T {
+    let previous = LOG.replace(Some(log.clone()));
+    let result = f();
+    LOG.set(previous);
+    result
+}
+
+pub(crate) fn silence() {
+    SILENCED.store(true, Ordering::Relaxed);
+}
+
+/// Prints what was logged, indented and tagged with `label`.
+pub(crate) fn print(label: &str, log: &Log) {
+    for line in log
+        .lock()
+        .unwrap()
+        .iter()
+        .flat_map(|message| message.lines())
+    {
+        println!("  [{label}] {line}");
+    }
+}
+
+/// Prints a message from a part with `--example` or `--verbose`, like `println!`. Compiles to
+/// nothing without the `debug` feature.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::debug::enabled() {
+            $crate::debug::__log(format_args!($($arg)*));
+        }
+    };
+}
+
+/// Like `dbg!`, prints an expression and its value with `--example` or `--verbose` and returns
+/// the value. Without the `debug` feature it's just the expression.
+#[macro_export]
+macro_rules! trace {
+    ($value:expr $(,)?) => {
+        match $value {
+            value => {
+                if $crate::debug::enabled() {
+                    $crate::debug::__log(format_args!(
+                        "[{}:{}] {} = {:#?}",
+                        file!(),
+                        line!(),
+                        stringify!($value),
+                        &value
+                    ));
+                }
+                value
+            }
+        }
+    };
+}
@@ -1,16 +1,23 @@
 // Re-export some common libs and imports
-pub use aoc_macro::{parse, part_one, part_two};
+pub use aoc_macro::{generator, parse, part_one, part_two};
 pub use color_eyre;
-pub use color_eyre::{Result, eyre::OptionExt, eyre::bail};
+pub use color_eyre::{Result, eyre::OptionExt, eyre::WrapErr, eyre::bail};
 pub use criterion;
 pub use inventory;
 pub use itertools::Itertools;
 pub use itertools::*;
+pub use proptest;
 pub use std::str::Lines;
 
 use reqwest::blocking::Client;
+use std::any::Any;
 use std::{fs::File, io::Write};
 
+mod check;
+pub mod debug;
+pub mod mem;
+pub mod viz;
+
 pub mod utils {
     // Source - https://stackoverflow.com/a
     // Posted by Netwave, modified by community. See post 'Timeline' for change history
@@ -30,146 +37,1282 @@ pub mod utils {
     }
 }
 
-pub fn __get_input(day: u32) -> Result<String> {
-    // Use workspace root for cache (where Cargo.toml with [workspace] lives)
-    let cache_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+/// Used when .aoc/config.toml doesn't set a year
+const DEFAULT_YEAR: u32 = 2025;
+
+/// Workspace root (where Cargo.toml with [workspace] lives)
+fn workspace_root() -> &'static std::path::Path {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
         .parent()
         .expect("common crate should be in workspace")
-        .join(".input");
+}
+
+fn read_config() -> Option<toml::Table> {
+    std::fs::read_to_string(workspace_root().join(".aoc/config.toml"))
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// Year for days that don't give one to `aoc_day!`, from `year` in .aoc/config.toml
+pub fn __default_year() -> u32 {
+    read_config()
+        .and_then(|config| config.get("year")?.as_integer())
+        .map(|year| year as u32)
+        .unwrap_or(DEFAULT_YEAR)
+}
+
+/// Caches used to live directly in `.input/`, back when every input was from this year
+const UNKEYED_CACHE_YEAR: u32 = 2025;
+
+/// Where inputs for `year` are cached under `root`. Caches from before they were keyed by year
+/// are moved into 2025's directory the first time it's looked for. Shared with `cargo aoc`, which
+/// passes the current directory.
+#[doc(hidden)]
+pub fn __input_dir(root: &std::path::Path, year: u32) -> std::path::PathBuf {
+    let input_dir = root.join(".input");
+    let year_dir = input_dir.join(year.to_string());
+    if year != UNKEYED_CACHE_YEAR {
+        return year_dir;
+    }
+
+    let Ok(entries) = std::fs::read_dir(&input_dir) else {
+        return year_dir;
+    };
+    for entry in entries.flatten() {
+        let (path, moved) = (entry.path(), year_dir.join(entry.file_name()));
+        if path.is_file()
+            && entry.file_name().to_string_lossy().starts_with("day")
+            && !moved.exists()
+        {
+            let _ = std::fs::create_dir_all(&year_dir);
+            let _ = std::fs::rename(&path, &moved);
+        }
+    }
+    year_dir
+}
+
+pub fn __get_input(year: u32, day: u32) -> Result<String> {
+    // Caches are keyed by year so old years can be replayed from the same workspace
+    let cache_dir = __input_dir(workspace_root(), year);
+
+    let key = __input_key(workspace_root())?;
 
     // Check for --example flag
     let use_example = std::env::args().any(|arg| arg == "--example");
 
     if use_example {
         let example_path = cache_dir.join(format!("day{day}_example"));
-        return std::fs::read_to_string(&example_path)
-            .map_err(|e| color_eyre::eyre::eyre!("Failed to read example input: {}", e));
+        return read_cache(&example_path, key.as_ref())?.ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "Failed to read example input: {} doesn't exist",
+                example_path.display()
+            )
+        });
     }
 
-    // Try and load from .input/{day}
+    // Try and load from .input/{year}/day{day}
     let cache_path = cache_dir.join(format!("day{day}"));
-    if let Ok(input) = std::fs::read_to_string(&cache_path) {
+    if let Some(input) = read_cache(&cache_path, key.as_ref())? {
         return Ok(input);
     }
 
     // Otherwise pull from AOC server
-    let client = Client::new();
-    let session_token = include_str!("../../.session").trim();
-    let input = client
-        .get(format!("https://adventofcode.com/2025/day/{day}/input"))
-        .header("Cookie", format!("session={session_token}"))
-        .send()?
-        .text()?;
+    let input = fetch_input(year, day)?;
 
     std::fs::create_dir_all(&cache_dir)?;
     let mut file = File::create(&cache_path)?;
     file.write_all(input.as_bytes())?;
 
+    if let Some(key) = &key {
+        std::fs::write(
+            cache_path.with_extension("enc"),
+            __encrypt_input(key, &input)?,
+        )?;
+    }
+
     Ok(input)
 }
 
+/// Reads a cached input, falling back to its encrypted copy (which is safe to commit)
+fn read_cache(
+    path: &std::path::Path,
+    key: Option<&chacha20poly1305::Key>,
+) -> Result<Option<String>> {
+    if let Ok(input) = std::fs::read_to_string(path) {
+        return Ok(Some(input));
+    }
+
+    if let Some(key) = key
+        && let Ok(encrypted) = std::fs::read(path.with_extension("enc"))
+    {
+        return __decrypt_input(key, &encrypted).map(Some);
+    }
+
+    Ok(None)
+}
+
+/// Reads the input cache key from `AOC_INPUT_KEY` or .aoc/input.key under `root`, as 64 hex
+/// digits. Shared with `cargo aoc input` so both always agree on the key.
+#[doc(hidden)]
+pub fn __input_key(root: &std::path::Path) -> Result<Option<chacha20poly1305::Key>> {
+    let key = match std::env::var("AOC_INPUT_KEY") {
+        Ok(key) => key,
+        Err(_) => match std::fs::read_to_string(root.join(".aoc/input.key")) {
+            Ok(key) => key,
+            Err(_) => return Ok(None),
+        },
+    };
+
+    let key: [u8; 32] = hex::decode(key.trim())
+        .ok()
+        .and_then(|key| key.try_into().ok())
+        .ok_or_eyre("Input key must be 64 hex digits")?;
+    Ok(Some(key.into()))
+}
+
+/// Encrypted inputs are stored as the nonce followed by the ciphertext. Shared with
+/// `cargo aoc input` so the format can't drift.
+#[doc(hidden)]
+pub fn __encrypt_input(key: &chacha20poly1305::Key, input: &str) -> Result<Vec<u8>> {
+    use chacha20poly1305::ChaCha20Poly1305;
+    use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = ChaCha20Poly1305::new(key)
+        .encrypt(&nonce, input.as_bytes())
+        .map_err(|_| color_eyre::eyre::eyre!("Failed to encrypt input"))?;
+    Ok([nonce.as_slice(), &ciphertext].concat())
+}
+
+#[doc(hidden)]
+pub fn __decrypt_input(key: &chacha20poly1305::Key, encrypted: &[u8]) -> Result<String> {
+    use chacha20poly1305::aead::{Aead, KeyInit};
+    use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+
+    if encrypted.len() < 12 {
+        bail!("Encrypted input is truncated");
+    }
+    let (nonce, ciphertext) = encrypted.split_at(12);
+    let input = ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| color_eyre::eyre::eyre!("Failed to decrypt input, is the key correct?"))?;
+    Ok(String::from_utf8(input)?)
+}
+
+/// Can be overridden with `AOC_URL`, e.g. to point at a mock server
+const AOC_URL: &str = "https://adventofcode.com";
+
+/// AoC asks automated tools to identify themselves
+const USER_AGENT: &str = "github.com/fluxehub/AdventOfCode2025 aoc runner";
+
+/// Minimum time between requests to the AoC server, shared across all days
+const REQUEST_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// `REQUEST_INTERVAL`, unless `AOC_REQUEST_INTERVAL` gives another number of seconds (e.g. `0`
+/// for a mock server)
+fn request_interval() -> std::time::Duration {
+    std::env::var("AOC_REQUEST_INTERVAL")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .and_then(|secs| std::time::Duration::try_from_secs_f64(secs).ok())
+        .unwrap_or(REQUEST_INTERVAL)
+}
+
+/// Waits until enough time has passed since the last request from any day in the workspace.
+fn throttle() -> Result<()> {
+    let marker = workspace_root().join(".input").join(".last_request");
+    let interval = request_interval();
+
+    if let Ok(elapsed) = std::fs::metadata(&marker)
+        .and_then(|m| m.modified())
+        .map(|modified| modified.elapsed().unwrap_or_default())
+        && elapsed < interval
+    {
+        std::thread::sleep(interval - elapsed);
+    }
+
+    std::fs::create_dir_all(marker.parent().unwrap())?;
+    File::create(&marker)?;
+    Ok(())
+}
+
+/// Turns the pages AoC serves instead of an input into errors, so they never get cached.
+fn check_input_response(status: reqwest::StatusCode, body: &str) -> Result<()> {
+    if body.contains("Please log in") {
+        bail!("Not logged in, check the session cookie in .session");
+    }
+    if body.contains("before it unlocks") {
+        bail!("Puzzle hasn't unlocked yet");
+    }
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        bail!("Rate limited by the AoC server, try again later");
+    }
+    if status == reqwest::StatusCode::NOT_FOUND {
+        bail!("Puzzle not found, check the year and day");
+    }
+    if !status.is_success() {
+        bail!("AoC server returned {status}: {}", body.trim());
+    }
+    if body.trim_start().starts_with('<') {
+        bail!("AoC server returned a web page instead of an input, is the session cookie valid?");
+    }
+    if body.trim().is_empty() {
+        bail!("AoC server returned an empty input");
+    }
+    Ok(())
+}
+
+/// Fetches `path` (e.g. `/2025/day/1`) from the AoC server, throttled along with every other
+/// request from the workspace. Also used by `cargo aoc read`.
+#[doc(hidden)]
+pub fn __request(path: &str, session: &str) -> Result<(reqwest::StatusCode, String)> {
+    throttle()?;
+
+    let base_url = std::env::var("AOC_URL").unwrap_or_else(|_| AOC_URL.to_string());
+    let client = Client::builder().user_agent(USER_AGENT).build()?;
+    let response = client
+        .get(format!("{base_url}{path}"))
+        .header("Cookie", format!("session={session}"))
+        .send()?;
+
+    let status = response.status();
+    Ok((status, response.text()?))
+}
+
+fn fetch_input(year: u32, day: u32) -> Result<String> {
+    let session_token = include_str!("../../.session").trim();
+    let (status, body) = __request(&format!("/{year}/day/{day}/input"), session_token)?;
+    check_input_response(status, &body)
+        .map_err(|e| e.wrap_err(format!("Failed to download input for {year} day {day}")))?;
+
+    Ok(body)
+}
+
+/// Registered by `aoc_day!`, so a binary linking several days can find each one's input and parser.
+pub struct AocDay {
+    /// `None` for days that use the year from .aoc/config.toml
+    pub year: Option<u32>,
+    pub day: u32,
+    pub input: fn() -> Result<String>,
+    /// `Solution::parse`, with the parsed data boxed up for the part wrappers.
+    ///
+    /// # Safety
+    /// The parsed data can borrow from the input, which has to outlive it.
+    pub parse: Parse,
+    /// Like `parse`, but `None` if the #[parse] function returned an error
+    pub try_parse: TryParse,
+    /// Stack size for the threads parts run on, e.g. `256M`
+    pub stack: Option<&'static str>,
+}
+
+type Parse = unsafe fn(&str) -> Box<dyn Any + Send + Sync>;
+type TryParse = unsafe fn(&str) -> Option<Box<dyn Any + Send + Sync>>;
+
+/// Parsed data along with the input it borrows from. Fields drop in order, so the data always
+/// goes before the input.
+struct Parsed {
+    data: Box<dyn Any + Send + Sync>,
+    input: std::sync::Arc<str>,
+}
+
+impl Parsed {
+    fn new(input: std::sync::Arc<str>, parse: Parse) -> Self {
+        // Safety: the input is kept alongside the data and dropped after it
+        let data = unsafe { parse(&input) };
+        Parsed { data, input }
+    }
+
+    fn try_new(input: std::sync::Arc<str>, try_parse: TryParse) -> Option<Self> {
+        // Safety: as above
+        let data = unsafe { try_parse(&input) }?;
+        Some(Parsed { data, input })
+    }
+}
+
+inventory::collect!(AocDay);
+
 pub struct AocPart {
+    pub day: u32,
     pub part: u8,
-    pub func: fn() -> String,
+    /// Name of the part function
+    pub name: &'static str,
+    /// Name given with `alt = "..."` for alternative implementations
+    pub alt: Option<&'static str>,
+    /// Takes part one's result, so the day's parts have to run one after the other
+    pub after_part_one: bool,
+    /// Slow reference implementation, only run against generated inputs
+    pub oracle: bool,
+    pub func: fn(&PartContext) -> PartAnswer,
 }
 
 inventory::collect!(AocPart);
 
+/// Random valid inputs for a day, registered by #[generator]
+pub struct AocGenerator {
+    pub day: u32,
+    pub strategy: fn() -> proptest::strategy::BoxedStrategy<String>,
+}
+
+inventory::collect!(AocGenerator);
+
+/// What a part's generated wrapper runs with. The runner owns the parsed data, so any number of
+/// inputs can be run in one process.
+pub struct PartContext<'a> {
+    input: &'a str,
+    parsed: &'a (dyn Any + Send + Sync),
+    part_one: Option<&'a dyn Any>,
+}
+
+impl PartContext<'_> {
+    /// The raw input, for parts that need to parse their own copy of the data
+    pub fn input(&self) -> &str {
+        self.input
+    }
+
+    /// The parsed data. Types that borrow from the input are asked for with a `'static`
+    /// lifetime, which the wrappers shorten to the context's again.
+    pub fn parsed<T: 'static>(&self) -> &T {
+        self.parsed
+            .downcast_ref()
+            .expect("Parsed data has the wrong type")
+    }
+
+    /// Part one's result, for parts marked `after_part_one`
+    pub fn part_one<T: 'static>(&self) -> &T {
+        self.part_one
+            .and_then(|result| result.downcast_ref())
+            .expect("Part one hasn't finished")
+    }
+}
+
+pub struct PartAnswer {
+    pub answer: String,
+    /// Part one's full result, when part two runs after it
+    pub kept: Option<Box<dyn Any>>,
+}
+
+impl AocPart {
+    /// How the part is shown in output, e.g. `Part 1` or `Part 1 (dp)`
+    pub fn label(&self) -> String {
+        match self.alt {
+            Some(alt) => format!("Part {} ({alt})", self.part),
+            None => format!("Part {}", self.part),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BenchKind {
+    /// Parsing only, registered by #[parse]
+    Parse,
+    /// A part's solve, with the input parsed once up front
+    Solve,
+    /// Parsing and solving together
+    Total,
+}
+
+/// Gives part functions that take their data by value or `&mut` a copy of their own: a clone
+/// when the parsed type is `Clone`, otherwise a fresh parse. The macros can't see whether a type
+/// is `Clone`, so they pick with autoref specialization.
+#[doc(hidden)]
+pub mod __fresh {
+    pub struct Fresh<'a, T>(pub &'a T);
+
+    pub trait ViaClone<T> {
+        fn fresh_copy(&self, reparse: impl FnOnce() -> T) -> T;
+    }
+
+    impl<T: Clone> ViaClone<T> for Fresh<'_, T> {
+        fn fresh_copy(&self, _reparse: impl FnOnce() -> T) -> T {
+            self.0.clone()
+        }
+    }
+
+    pub trait ViaParse<T> {
+        fn fresh_copy(&self, reparse: impl FnOnce() -> T) -> T;
+    }
+
+    impl<T> ViaParse<T> for &Fresh<'_, T> {
+        fn fresh_copy(&self, reparse: impl FnOnce() -> T) -> T {
+            reparse()
+        }
+    }
+}
+
 pub struct AocBench {
+    pub day: u32,
+    /// Part number, or 0 for parsing
     pub part: u8,
-    pub func: fn(&str) -> String,
+    /// Name of the parse or part function
+    pub name: &'static str,
+    /// Name given with `alt = "..."` for alternative implementations
+    pub alt: Option<&'static str>,
+    pub kind: BenchKind,
+    pub func: fn(&mut criterion::Bencher, &str),
 }
 
 inventory::collect!(AocBench);
 
-/// Default parse implementation for when no #[parse] is defined.
-/// Local definitions from #[parse] will shadow these via wildcard import.
-pub mod __aoc_defaults {
-    use std::sync::OnceLock;
+/// The parts registered for a day, in order, with each main implementation before its alternatives.
+/// Oracles are left out.
+fn day_parts(day: u32) -> Vec<&'static AocPart> {
+    inventory::iter::<AocPart>
+        .into_iter()
+        .filter(|p| p.day == day && !p.oracle)
+        .sorted_by_key(|p| (p.part, p.alt))
+        .collect()
+}
+
+/// Entry point for the fuzz target `cargo aoc fuzz` generates. Runs every part (but not oracles)
+/// on this thread, so panics reach the fuzzer. Inputs the #[parse] function returns an error for
+/// are skipped.
+pub fn __fuzz(day: u32, input: &str) {
+    let aoc_day = inventory::iter::<AocDay>
+        .into_iter()
+        .find(|d| d.day == day)
+        .expect("aoc_day! wasn't used for this day");
+    let Some(parsed) = Parsed::try_new(input.into(), aoc_day.try_parse) else {
+        return;
+    };
+
+    let mut part_one = None;
+    for part in day_parts(day) {
+        let context = PartContext {
+            input: &parsed.input,
+            parsed: &*parsed.data,
+            part_one: part_one.as_deref(),
+        };
+        let result = (part.func)(&context);
+        if result.kept.is_some() {
+            part_one = result.kept;
+        }
+    }
+}
 
-    pub static __PARSED_DATA: OnceLock<String> = OnceLock::new();
+/// Describes each alternative implementation whose answer doesn't match the main implementation.
+/// `answers` lines up with `parts`, with `None` for anything that didn't finish.
+fn disagreements(parts: &[&AocPart], answers: &[Option<&String>]) -> Vec<String> {
+    let mut disagreements = vec![];
+    for (part, answer) in parts.iter().zip(answers) {
+        let Some(main) = parts
+            .iter()
+            .position(|p| p.part == part.part && p.alt.is_none())
+        else {
+            continue;
+        };
+        if let (Some(alt), Some(answer), Some(expected)) = (part.alt, answer, answers[main])
+            && *answer != expected
+        {
+            disagreements.push(format!(
+                "Part {}: {alt} gave {answer}, but {} gave {expected}",
+                part.part, parts[main].name
+            ));
+        }
+    }
+    disagreements
+}
 
-    /// Returns parsed data (can be called multiple times, for benchmarks)
-    pub fn __do_parse(text: &str) -> String {
-        text.to_string()
+/// Flags alternative implementations whose answer doesn't match the main implementation.
+fn check_alternatives(parts: &[&AocPart], answers: &[Option<&String>]) -> Result<()> {
+    let disagreements = disagreements(parts, answers);
+    for disagreement in &disagreements {
+        eprintln!("{disagreement}");
     }
 
-    pub fn __parse_data(text: &str) {
-        __PARSED_DATA.set(__do_parse(text)).unwrap();
+    if !disagreements.is_empty() {
+        bail!("Alternative implementations disagree");
     }
+    Ok(())
 }
 
-pub fn __run_day() {
-    std::thread::scope(|s| {
-        let handles: Vec<_> = inventory::iter::<AocPart>
-            .into_iter()
-            .sorted_by_key(|p| p.part)
-            .map(|part| (part.part, s.spawn(|| (part.func)())))
-            .collect();
+/// Entry point generated by `aoc_day!`.
+pub fn __main(day: u32) -> Result<()> {
+    let aoc_day = inventory::iter::<AocDay>
+        .into_iter()
+        .find(|d| d.day == day)
+        .ok_or_eyre("aoc_day! wasn't used for this day")?;
+
+    if std::env::args().any(|arg| arg == "--check") {
+        color_eyre::install()?;
+        return check::check_day(aoc_day);
+    }
+
+    let input = (aoc_day.input)()?;
+
+    if std::env::args().any(|arg| arg == "--bench") {
+        __run_benchmarks(aoc_day, &input);
+        return Ok(());
+    }
+
+    color_eyre::install()?;
+    run_day(aoc_day, &input)
+}
+
+/// Entry point for the all-days binary: `run <DAY|all>` or `bench <DAY|all>`.
+pub fn __run_all() -> Result<()> {
+    // Flags like --timeout come after these, along with their values
+    let args = std::env::args().skip(1).take(2).collect_vec();
+    let (command, which) = match args.as_slice() {
+        [command, which] if !which.starts_with("--") => (command.as_str(), which.as_str()),
+        _ => bail!("Usage: aoc-all <run|bench> <DAY|all> [--example]"),
+    };
+
+    let days = inventory::iter::<AocDay>
+        .into_iter()
+        .filter(|d| which == "all" || which.parse() == Ok(d.day))
+        .sorted_by_key(|d| d.day)
+        .collect_vec();
+    if days.is_empty() {
+        bail!("No day {which} linked into this binary");
+    }
 
-        for (part, handle) in handles {
-            println!("Part {}: {}", part, handle.join().unwrap());
+    match command {
+        "run" => {
+            color_eyre::install()?;
+            let mut failed = vec![];
+            for day in days {
+                println!("Day {}", day.day);
+                let result = (day.input)().and_then(|input| run_day(day, &input));
+                if let Err(e) = result {
+                    eprintln!("Error: {e:#}");
+                    failed.push(day.day);
+                }
+                println!();
+            }
+            if !failed.is_empty() {
+                bail!("Failed days: {}", failed.iter().join(", "));
+            }
         }
+        "bench" => {
+            for day in days {
+                let input = (day.input)()?;
+                // Criterion's own argument parsing doesn't understand our subcommands
+                run_benchmarks(bench_criterion(day.day), day, &input);
+            }
+        }
+        _ => bail!("Unknown command '{command}', expected run or bench"),
+    }
+    Ok(())
+}
+
+/// How parts get run, from the command line and `aoc_day!`
+struct RunOptions {
+    timeout: Option<std::time::Duration>,
+    show_time: bool,
+    /// Most parts to run at once
+    threads: Option<usize>,
+    stack_size: Option<usize>,
+}
+
+impl RunOptions {
+    fn from_args(day: &AocDay) -> Self {
+        let invalid = |message: String| -> ! {
+            eprintln!("{message}");
+            std::process::exit(1);
+        };
+
+        let timeout = arg_value("--timeout").map(|timeout| {
+            parse_duration(&timeout).unwrap_or_else(|| {
+                invalid(format!(
+                    "Invalid timeout '{timeout}', expected e.g. 30s, 500ms or 2m"
+                ))
+            })
+        });
+        let threads = arg_value("--threads").map(|threads| match threads.parse() {
+            Ok(threads) if threads > 0 => threads,
+            _ => invalid(format!("Invalid thread count '{threads}'")),
+        });
+        let stack_size = day.stack.map(|stack| {
+            parse_size(stack).unwrap_or_else(|| {
+                invalid(format!(
+                    "Invalid stack size '{stack}', expected e.g. 256M, 512K or 1G"
+                ))
+            })
+        });
+
+        RunOptions {
+            timeout,
+            show_time: std::env::args().any(|arg| arg == "--time"),
+            threads: match std::env::args().any(|arg| arg == "--sequential") {
+                true => Some(1),
+                false => threads,
+            },
+            stack_size,
+        }
+    }
+
+    fn thread_builder(&self) -> std::thread::Builder {
+        let builder = std::thread::Builder::new();
+        match self.stack_size {
+            Some(size) => builder.stack_size(size),
+            None => builder,
+        }
+    }
+}
+
+fn run_day(day: &AocDay, input: &str) -> Result<()> {
+    let options = RunOptions::from_args(day);
+
+    // Parts that use rayon share its global pool, so cap that too
+    if let Some(threads) = arg_value("--threads").and_then(|threads| threads.parse().ok()) {
+        // Fails if the pool already exists, e.g. for the second day in aoc-all
+        let _ = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global();
+    }
+
+    if !viz::ENABLED && std::env::args().any(|arg| arg == "--viz") {
+        eprintln!("--viz needs the aoc/viz feature, use cargo aoc run --viz");
+        std::process::exit(1);
+    }
+
+    if viz::enabled() {
+        // Clear out frames from parts that no longer record any
+        let _ = std::fs::remove_dir_all(workspace_root().join(format!(".viz/day{:02}", day.day)));
+    }
+
+    if std::env::args().any(|arg| arg == "--mem") {
+        return run_day_with_mem(day.day, input, day.parse, &options);
+    }
+
+    let input: std::sync::Arc<str> = input.into();
+    let log = debug::Log::default();
+    let start = std::time::Instant::now();
+    let parsed = debug::capture(&log, || Parsed::new(input, day.parse));
+    save_viz(day.day, None);
+    if options.show_time {
+        println!("Parse ({:.2?})", start.elapsed());
+    }
+    debug::print("Parse", &log);
+
+    run_parts(day.day, parsed.into(), &options)
+}
+
+/// Parts are identified by their index in `day_parts`, since alternatives share a part number
+enum PartEvent {
+    Started(usize, std::time::Instant),
+    Finished(usize, std::thread::Result<(String, std::time::Duration)>),
+    Interrupted,
+}
+
+enum PartOutcome {
+    Finished(String, std::time::Duration),
+    Panicked,
+    TimedOut,
+    /// Queued on the same thread as a part that timed out
+    Skipped,
+    Interrupted,
+}
+
+/// Where Ctrl-C gets reported to while parts are running
+static INTERRUPT_SENDER: std::sync::Mutex<Option<std::sync::mpsc::Sender<PartEvent>>> =
+    std::sync::Mutex::new(None);
+
+/// Routes Ctrl-C to the parts currently running. The handler can only be installed once per
+/// process, so it's shared between days when several run in one process.
+fn set_interrupt_sender(sender: std::sync::mpsc::Sender<PartEvent>) {
+    static HANDLER: std::sync::Once = std::sync::Once::new();
+
+    *INTERRUPT_SENDER.lock().unwrap() = Some(sender);
+    HANDLER.call_once(|| {
+        // Only fails if something else already set a handler, in which case it's in charge
+        let _ = ctrlc::set_handler(|| match INTERRUPT_SENDER.lock().unwrap().as_ref() {
+            Some(sender) => {
+                let _ = sender.send(PartEvent::Interrupted);
+            }
+            None => std::process::exit(130),
+        });
     });
 }
 
-pub fn __run_benchmarks(day: u32, input: &str) {
+/// Runs both parts (and any alternatives) in parallel, unless part two runs after part one,
+/// giving up on any still running after the timeout or a Ctrl-C.
+/// Threads can't be killed, so anything left over dies when the process exits.
+fn run_parts(day: u32, parsed: std::sync::Arc<Parsed>, options: &RunOptions) -> Result<()> {
+    use std::collections::BTreeMap;
+    use std::sync::mpsc::{self, RecvTimeoutError};
+
+    let (sender, receiver) = mpsc::channel();
+    set_interrupt_sender(sender.clone());
+
+    let parts = day_parts(day);
+    // When part two needs part one's result, everything runs in order on a single thread.
+    // Otherwise parts are shared out between the threads, keeping their order on each.
+    let thread_count = match parts.iter().any(|p| p.after_part_one) {
+        true => 1,
+        false => options.threads.unwrap_or(parts.len()).min(parts.len()),
+    };
+    let logs = parts.iter().map(|_| debug::Log::default()).collect_vec();
+    let mut threads = vec![vec![]; thread_count];
+    for (i, part) in parts.iter().copied().enumerate() {
+        threads[i % thread_count].push((i, part, logs[i].clone()));
+    }
+    let queues = threads
+        .iter()
+        .map(|thread| thread.iter().map(|(i, ..)| *i).collect_vec())
+        .collect_vec();
+    for thread in threads {
+        let sender = sender.clone();
+        let parsed = parsed.clone();
+        let spawned = options.thread_builder().spawn(move || {
+            let mut part_one = None;
+            for (i, part, log) in thread {
+                let context = PartContext {
+                    input: &parsed.input,
+                    parsed: &*parsed.data,
+                    part_one: part_one.as_deref(),
+                };
+                let start = std::time::Instant::now();
+                let _ = sender.send(PartEvent::Started(i, start));
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    let answer = debug::capture(&log, || (part.func)(&context));
+                    (answer, start.elapsed())
+                }));
+                save_viz(day, Some(part));
+                let result = result.map(|(answer, elapsed)| {
+                    if answer.kept.is_some() {
+                        part_one = answer.kept;
+                    }
+                    (answer.answer, elapsed)
+                });
+                let _ = sender.send(PartEvent::Finished(i, result));
+            }
+        });
+        spawned.wrap_err("Failed to start a thread for the parts")?;
+    }
+
+    let (timeout, show_time) = (options.timeout, options.show_time);
+    let mut outcomes = BTreeMap::new();
+    let mut unfinished = None;
+    // When each running part started. Every part gets the whole timeout to itself, even when it
+    // was queued behind others on the same thread.
+    let mut running: BTreeMap<usize, std::time::Instant> = BTreeMap::new();
+
+    while outcomes.len() < parts.len() {
+        let deadline = timeout.and_then(|timeout| Some(*running.values().min()? + timeout));
+        let event = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(std::time::Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match event {
+            // Parts that already timed out (or were skipped) can still get here late
+            Ok(PartEvent::Started(part, _) | PartEvent::Finished(part, _))
+                if outcomes.contains_key(&part) => {}
+            Ok(PartEvent::Started(part, start)) => {
+                running.insert(part, start);
+            }
+            Ok(PartEvent::Finished(part, result)) => {
+                running.remove(&part);
+                let outcome = match result {
+                    Ok((result, elapsed)) => PartOutcome::Finished(result, elapsed),
+                    Err(_) => PartOutcome::Panicked,
+                };
+                outcomes.insert(part, outcome);
+            }
+            Ok(PartEvent::Interrupted) => {
+                unfinished = Some(PartOutcome::Interrupted);
+                break;
+            }
+            Err(_) => {
+                // Only running parts have deadlines, and the one that started first is out of time
+                let (&part, _) = running
+                    .iter()
+                    .min_by_key(|(_, start)| **start)
+                    .expect("only running parts time out");
+                running.remove(&part);
+                outcomes.insert(part, PartOutcome::TimedOut);
+
+                // Its thread is stuck, so whatever was queued after it never runs
+                let queue = queues.iter().find(|queue| queue.contains(&part)).unwrap();
+                for &queued in queue.iter().skip_while(|&&i| i != part).skip(1) {
+                    outcomes.entry(queued).or_insert(PartOutcome::Skipped);
+                }
+            }
+        }
+    }
+
+    let mut failed = unfinished.is_some();
+    for (i, part) in parts.iter().enumerate() {
+        let label = part.label();
+        match outcomes.get(&i).or(unfinished.as_ref()).unwrap() {
+            PartOutcome::Finished(result, elapsed) if show_time => {
+                println!("{label}: {result} ({elapsed:.2?})")
+            }
+            PartOutcome::Finished(result, _) => println!("{label}: {result}"),
+            PartOutcome::Panicked => {
+                failed = true;
+                println!("{label}: panicked");
+            }
+            PartOutcome::TimedOut => {
+                failed = true;
+                println!("{label}: timed out after {:?}", timeout.unwrap());
+            }
+            PartOutcome::Skipped => {
+                failed = true;
+                println!("{label}: skipped, an earlier part on its thread timed out");
+            }
+            PartOutcome::Interrupted => println!("{label}: interrupted"),
+        }
+        debug::print(&label, &logs[i]);
+    }
+
+    *INTERRUPT_SENDER.lock().unwrap() = None;
+    if let Some(PartOutcome::Interrupted) = unfinished {
+        std::process::exit(130);
+    }
+
+    let answers = (0..parts.len())
+        .map(|i| match outcomes.get(&i) {
+            Some(PartOutcome::Finished(result, _)) => Some(result),
+            _ => None,
+        })
+        .collect_vec();
+    check_alternatives(&parts, &answers)?;
+
+    if failed {
+        bail!("Not every part finished");
+    }
+    Ok(())
+}
+
+/// Writes the frames recorded with `viz::frame` to `.viz/dayNN/partN`, or `.viz/dayNN/parse`
+/// when `part` is `None`.
+fn save_viz(day: u32, part: Option<&AocPart>) {
+    if !viz::enabled() {
+        return;
+    }
+    let name = match part {
+        Some(AocPart {
+            part,
+            alt: Some(alt),
+            ..
+        }) => format!("part{part}-{}", alt.replace(' ', "-")),
+        Some(AocPart { part, .. }) => format!("part{part}"),
+        None => "parse".to_string(),
+    };
+    let dir = workspace_root()
+        .join(".viz")
+        .join(format!("day{day:02}"))
+        .join(name);
+    if let Err(e) = viz::save(&dir) {
+        eprintln!("Failed to save frames: {e:#}");
+    }
+}
+
+/// Value following a flag on the command line, e.g. `--timeout 30s`.
+fn arg_value(flag: &str) -> Option<String> {
+    std::env::args().skip_while(|arg| arg != flag).nth(1)
+}
+
+/// Parses sizes like `256M`, `512K` or `1G`, with bare numbers taken as bytes.
+fn parse_size(size: &str) -> Option<usize> {
+    let split = size
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(size.len());
+    let (value, unit) = size.split_at(split);
+    let value: usize = value.parse().ok()?;
+
+    let multiplier = match unit.to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        _ => return None,
+    };
+    value.checked_mul(multiplier)
+}
+
+/// Parses durations like `30s`, `500ms` or `2m`, with bare numbers taken as seconds.
+fn parse_duration(duration: &str) -> Option<std::time::Duration> {
+    let split = duration
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(duration.len());
+    let (value, unit) = duration.split_at(split);
+    let value: f64 = value.parse().ok()?;
+
+    let secs = match unit {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        _ => return None,
+    };
+    Some(std::time::Duration::from_secs_f64(secs))
+}
+
+/// Runs everything one at a time so each allocation is counted against the right step.
+fn run_day_with_mem(day: u32, input: &str, parse: Parse, options: &RunOptions) -> Result<()> {
+    if !mem::ENABLED {
+        eprintln!("--mem needs the aoc/mem feature, use cargo aoc run --mem");
+        std::process::exit(1);
+    }
+
+    let input: std::sync::Arc<str> = input.into();
+    let log = debug::Log::default();
+    let (parsed, parse_stats) = mem::measure(|| debug::capture(&log, || Parsed::new(input, parse)));
+    save_viz(day, None);
+    debug::print("Parse", &log);
+    let mut stats = vec![("Parse".to_string(), parse_stats)];
+
+    let parts = day_parts(day);
+    let mut answers = vec![];
+    // Still on a thread of its own, for the stack size
+    std::thread::scope(|scope| {
+        let thread = options.thread_builder().spawn_scoped(scope, || {
+            let mut part_one = None;
+            for part in &parts {
+                let context = PartContext {
+                    input: &parsed.input,
+                    parsed: &*parsed.data,
+                    part_one: part_one.as_deref(),
+                };
+                let log = debug::Log::default();
+                let (result, part_stats) =
+                    mem::measure(|| debug::capture(&log, || (part.func)(&context)));
+                save_viz(day, Some(part));
+                println!("{}: {}", part.label(), result.answer);
+                debug::print(&part.label(), &log);
+                answers.push(result.answer);
+                if result.kept.is_some() {
+                    part_one = result.kept;
+                }
+                stats.push((part.label(), part_stats));
+            }
+        })?;
+        if let Err(panic) = thread.join() {
+            std::panic::resume_unwind(panic);
+        }
+        Ok::<_, std::io::Error>(())
+    })
+    .wrap_err("Failed to start a thread for the parts")?;
+
+    let width = stats.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    println!();
+    println!(
+        "{:<width$} {:>12} {:>12} {:>12}",
+        "", "Allocations", "Allocated", "Peak"
+    );
+    for (name, stats) in stats {
+        println!(
+            "{:<width$} {:>12} {:>12} {:>12}",
+            name,
+            stats.allocations,
+            mem::format_bytes(stats.allocated),
+            mem::format_bytes(stats.peak)
+        );
+    }
+
+    check_alternatives(&parts, &answers.iter().map(Some).collect_vec())
+}
+
+pub fn __run_benchmarks(day: &AocDay, input: &str) {
+    run_benchmarks(bench_criterion(day.day).configure_from_args(), day, input);
+}
+
+/// Prefix of the day's benchmark names, with the year for days outside the configured one so
+/// they don't share Criterion's results (the same as the day's package name)
+fn bench_prefix(day: &AocDay) -> String {
+    match day.year {
+        Some(year) if year != __default_year() => format!("day{:02}-{year}", day.day),
+        _ => format!("day{:02}", day.day),
+    }
+}
+
+fn bench_criterion(day: u32) -> criterion::Criterion {
     use criterion::Criterion;
     use std::time::Duration;
 
-    let mut criterion = Criterion::default()
-        .measurement_time(Duration::from_secs(10))
-        .configure_from_args();
+    // Settings come from [bench] in .aoc/config.toml, overridden by [bench.dayNN] for this day.
+    // Command line flags (forwarded by cargo aoc bench) win over both.
+    let config = read_config();
+    let setting = |key: &str| {
+        let bench = config.as_ref()?.get("bench")?;
+        bench
+            .get(format!("day{day:02}"))
+            .and_then(|day| day.get(key))
+            .or_else(|| bench.get(key))
+            .and_then(|value| value.as_float().or(value.as_integer().map(|i| i as f64)))
+    };
+
+    let mut criterion = Criterion::default().measurement_time(Duration::from_secs(10));
+    if let Some(secs) = setting("measurement_time") {
+        criterion = criterion.measurement_time(Duration::from_secs_f64(secs));
+    }
+    if let Some(secs) = setting("warm_up_time") {
+        criterion = criterion.warm_up_time(Duration::from_secs_f64(secs));
+    }
+    if let Some(samples) = setting("sample_size") {
+        criterion = criterion.sample_size(samples as usize);
+    }
+    criterion
+}
 
+fn run_benchmarks(mut criterion: criterion::Criterion, day: &AocDay, input: &str) {
+    debug::silence();
+    let prefix = bench_prefix(day);
     for bench in inventory::iter::<AocBench>
         .into_iter()
-        .sorted_by_key(|b| b.part)
+        .filter(|b| b.day == day.day)
+        .sorted_by_key(|b| (b.part, b.kind, b.alt))
     {
-        let name = format!("day{:02} part {}", day, bench.part);
-        criterion.bench_function(&name, |b| {
-            b.iter(|| (bench.func)(std::hint::black_box(input)))
-        });
+        // Alternatives sit right after the main implementation, e.g. `day07 part 1 dp (total)`
+        let part = match bench.alt {
+            Some(alt) => format!("part {} {alt}", bench.part),
+            None => format!("part {}", bench.part),
+        };
+        let name = match bench.kind {
+            BenchKind::Parse => format!("{prefix} parse"),
+            BenchKind::Solve => format!("{prefix} {part} (solve only)"),
+            BenchKind::Total => format!("{prefix} {part} (total)"),
+        };
+        criterion.bench_function(&name, |b| (bench.func)(b, input));
     }
 
     criterion.final_summary();
 }
 
+/// Registers the day and generates its entry point. Solutions live in the day crate's library so
+/// the all-days binary can link them, and `src/main.rs` just calls the generated `main`.
 #[macro_export]
 macro_rules! aoc_day {
+    (year = $year:expr, day = $day:expr, stack = $stack:expr) => {
+        aoc::aoc_day!(@day Some($year), $day, || aoc::__get_input($year, $day), Some($stack));
+    };
+    (year = $year:expr, day = $day:expr) => {
+        aoc::aoc_day!(@day Some($year), $day, || aoc::__get_input($year, $day), None);
+    };
+    ($day:expr, stack = $stack:expr) => {
+        aoc::aoc_day!(@day None, $day, || aoc::__get_input(aoc::__default_year(), $day), Some($stack));
+    };
     ($day:expr) => {
+        aoc::aoc_day!(@day None, $day, || aoc::__get_input(aoc::__default_year(), $day), None);
+    };
+    ($day:expr, $input:expr) => {
+        aoc::aoc_day!(@day None, $day, || Ok($input.to_string()), None);
+    };
+    (@day $year:expr, $day:expr, $get_input:expr, $stack:expr) => {
         #[allow(unused_imports)]
-        use aoc::__aoc_defaults::*;
+        use __aoc_defaults::*;
 
-        fn main() -> Result<()> {
-            let input = aoc::__get_input($day)?;
+        /// Default parse implementation for when no #[parse] is defined.
+        /// Local definitions from #[parse] will shadow these via wildcard import.
+        #[allow(dead_code)]
+        mod __aoc_defaults {
+            /// Returns parsed data (can be called multiple times, for benchmarks)
+            pub fn __do_parse(text: &str) -> &str {
+                text
+            }
 
-            if std::env::args().any(|arg| arg == "--bench") {
-                aoc::__run_benchmarks($day, &input);
-                return Ok(());
+            pub fn __try_parse(text: &str) -> Option<&str> {
+                Some(text)
             }
 
-            color_eyre::install()?;
-            __parse_data(&input);
-            aoc::__run_day();
-            Ok(())
+            pub type __ParsedData<'a> = &'a str;
+
+            /// Only kept when part two is marked `after_part_one`
+            pub fn __keep_part_one<T>(_result: T) -> Option<Box<dyn std::any::Any>> {
+                None
+            }
         }
-    };
-    ($day:expr, $input:expr) => {
-        #[allow(unused_imports)]
-        use aoc::__aoc_defaults::*;
 
-        fn main() -> Result<()> {
-            let input: &str = &$input;
+        /// Ties parsed data stored as `'static` back to the borrow of the context it came from, so
+        /// parts can't hold on to it. Only compiles for types that can be shortened like this.
+        #[allow(dead_code)]
+        fn __shorten<'a>(data: &'a __ParsedData<'static>) -> &'a __ParsedData<'a> {
+            data
+        }
+
+        /// The day's solution. `#[part_one]` and `#[part_two]` add `part_one` and `part_two`,
+        /// which take the parsed data the same way the part functions do.
+        pub struct Solution;
 
-            if std::env::args().any(|arg| arg == "--bench") {
-                aoc::__run_benchmarks($day, input);
-                return Ok(());
+        impl Solution {
+            pub fn parse(input: &str) -> __ParsedData<'_> {
+                __do_parse(input)
             }
+        }
 
-            color_eyre::install()?;
-            __parse_data(input);
-            aoc::__run_day();
-            Ok(())
+        const __AOC_DAY: u32 = $day;
+
+        inventory::submit! {
+            aoc::AocDay {
+                year: $year,
+                day: $day,
+                input: $get_input,
+                // The runner keeps the input alive for as long as the parsed data. Types that
+                // don't borrow from it transmute to themselves.
+                #[allow(clippy::useless_transmute)]
+                parse: |input| unsafe {
+                    Box::new(std::mem::transmute::<__ParsedData<'_>, __ParsedData<'static>>(
+                        Solution::parse(input),
+                    ))
+                },
+                #[allow(clippy::useless_transmute)]
+                try_parse: |input| unsafe {
+                    let data = __try_parse(input)?;
+                    Some(Box::new(std::mem::transmute::<
+                        __ParsedData<'_>,
+                        __ParsedData<'static>,
+                    >(data)))
+                },
+                stack: $stack,
+            }
+        }
+
+        pub fn main() -> Result<()> {
+            aoc::__main($day)
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{__decrypt_input, __encrypt_input, __get_input, workspace_root};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::Once;
+
+    /// Far enough back that it never collides with a real cache
+    const YEAR: u32 = 1999;
+
+    /// What the mock server answers for each day's input
+    fn response(day: u32) -> (u16, &'static str) {
+        match day {
+            1 => (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+            2 => (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+            3 => (429, "Too Many Requests"),
+            4 => (
+                200,
+                "<!DOCTYPE html>\n<html><body>Advent of Code</body></html>",
+            ),
+            5 => (200, ""),
+            6 => (200, "1\n2\n3\n"),
+            _ => (500, "Unexpected request"),
+        }
+    }
+
+    /// Starts the mock server and points `AOC_URL` at it, once for every test.
+    fn mock_server() {
+        static START: Once = Once::new();
+        START.call_once(|| {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let address = listener.local_addr().unwrap();
+
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = stream.unwrap();
+                    let mut reader = BufReader::new(&stream);
+                    let mut request = String::new();
+                    reader.read_line(&mut request).unwrap();
+                    // Skip the headers
+                    let mut line = String::new();
+                    while reader.read_line(&mut line).unwrap() > 2 {
+                        line.clear();
+                    }
+
+                    let day = request
+                        .split('/')
+                        .nth(3)
+                        .and_then(|day| day.parse().ok())
+                        .unwrap_or(0);
+                    let (status, body) = response(day);
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                }
+            });
+
+            // SAFETY: every test waits on this before anything reads the environment
+            unsafe {
+                std::env::set_var("AOC_URL", format!("http://{address}"));
+                std::env::set_var("AOC_REQUEST_INTERVAL", "0");
+            }
+        });
+    }
+
+    /// Fetches a day's input, checking it fails with `error` without caching anything.
+    fn assert_not_cached(day: u32, error: &str) {
+        mock_server();
+        let cache_path = workspace_root()
+            .join(".input")
+            .join(YEAR.to_string())
+            .join(format!("day{day}"));
+
+        let report = __get_input(YEAR, day).unwrap_err();
+        assert!(
+            format!("{report:?}").contains(error),
+            "expected '{error}' in {report:?}"
+        );
+        assert!(!cache_path.exists());
+        assert!(!cache_path.with_extension("enc").exists());
+    }
+
+    #[test]
+    fn not_logged_in() {
+        assert_not_cached(1, "Not logged in");
+    }
+
+    #[test]
+    fn not_unlocked() {
+        assert_not_cached(2, "hasn't unlocked yet");
+    }
+
+    #[test]
+    fn rate_limited() {
+        assert_not_cached(3, "Rate limited");
+    }
+
+    #[test]
+    fn web_page() {
+        assert_not_cached(4, "web page instead of an input");
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_not_cached(5, "empty input");
+    }
+
+    /// `.enc` files are committed, so the format can't change without breaking them
+    #[test]
+    fn encrypted_input_format() {
+        let key = [7; 32].into();
+        let committed =
+            hex::decode("0b4e802a19ae5b7193b99d7512119847ea462325ba2af44b33b5d4f953475846bc8d")
+                .unwrap();
+        assert_eq!(__decrypt_input(&key, &committed).unwrap(), "1\n2\n3\n");
+
+        // A random 12 byte nonce, then the ciphertext with its 16 byte tag
+        let encrypted = __encrypt_input(&key, "1\n2\n3\n").unwrap();
+        assert_eq!(encrypted.len(), 12 + 6 + 16);
+        assert_eq!(__decrypt_input(&key, &encrypted).unwrap(), "1\n2\n3\n");
+        assert!(__decrypt_input(&[8; 32].into(), &encrypted).is_err());
+    }
+
+    #[test]
+    fn good_input() {
+        mock_server();
+        let cache_dir = workspace_root().join(".input").join(YEAR.to_string());
+        let cache_path = cache_dir.join("day6");
+
+        let input = __get_input(YEAR, 6).unwrap();
+        let cached = std::fs::read_to_string(&cache_path);
+        let _ = std::fs::remove_file(&cache_path);
+        let _ = std::fs::remove_file(cache_path.with_extension("enc"));
+        let _ = std::fs::remove_dir(&cache_dir);
+
+        assert_eq!(input, "1\n2\n3\n");
+        assert_eq!(cached.unwrap(), input);
+    }
+}
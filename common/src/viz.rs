@@ -0,0 +1,217 @@
+//! Frame recording for `--viz`. Parts push grids with `viz::frame` as they go, and each part's
+//! frames are written to `.viz/dayNN/` as PNGs, an animated GIF and a `.frames` file for
+//! `cargo aoc step` once it finishes.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+pub const ENABLED: bool = cfg!(feature = "viz");
+
+pub type Rgb = [u8; 3];
+
+/// A `width` by `height` grid of cells, indexed by `(x, y)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the grid"
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the grid"
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Already coloured, so parts can drop their grid straight away
+#[cfg_attr(not(feature = "viz"), allow(dead_code))]
+struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+    counters: Vec<(String, String)>,
+}
+
+thread_local! {
+    /// Frames from the part running on this thread
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(vec![]) };
+    /// Latest value of each counter, in the order they were first set
+    static COUNTERS: RefCell<Vec<(String, String)>> = const { RefCell::new(vec![]) };
+}
+
+/// Whether frames are being recorded. Building a grid only to visualize it is worth skipping when
+/// this is false, which it always is without the `viz` feature.
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED && recording()
+}
+
+#[cfg(feature = "viz")]
+fn recording() -> bool {
+    static RECORDING: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *RECORDING.get_or_init(|| std::env::args().any(|arg| arg == "--viz"))
+}
+
+#[cfg(not(feature = "viz"))]
+fn recording() -> bool {
+    false
+}
+
+/// Sets a counter shown next to this and later frames in `cargo aoc step`.
+#[inline]
+pub fn counter(name: &str, value: impl Display) {
+    if !enabled() {
+        return;
+    }
+    COUNTERS.with_borrow_mut(|counters| {
+        let value = value.to_string();
+        match counters.iter_mut().find(|(counter, _)| counter == name) {
+            Some((_, old)) => *old = value,
+            None => counters.push((name.to_string(), value)),
+        }
+    });
+}
+
+/// Records `grid` as the next frame, with each cell coloured by `colour`.
+#[inline]
+pub fn frame<T>(grid: &Grid<T>, colour: impl Fn(&T) -> Rgb) {
+    if !enabled() {
+        return;
+    }
+    let frame = Frame {
+        width: grid.width,
+        height: grid.height,
+        pixels: grid.cells.iter().map(colour).collect(),
+        counters: COUNTERS.with_borrow(|counters| counters.clone()),
+    };
+    FRAMES.with_borrow_mut(|frames| frames.push(frame));
+}
+
+/// Writes the frames recorded on this thread to `dir`, plus `dir` with a `.gif` and `.frames`
+/// extension.
+#[cfg(feature = "viz")]
+pub(crate) fn save(dir: &std::path::Path) -> crate::Result<()> {
+    use crate::WrapErr;
+    use image::codecs::gif::{GifEncoder, Repeat};
+    use image::{Delay, RgbImage, RgbaImage};
+
+    COUNTERS.take();
+    let frames = FRAMES.take();
+    if frames.is_empty() {
+        return Ok(());
+    }
+    std::fs::create_dir_all(dir).wrap_err_with(|| format!("Failed to create {dir:?}"))?;
+
+    let frames_path = dir.with_extension("frames");
+    std::fs::write(&frames_path, frames_file(&frames))
+        .wrap_err_with(|| format!("Failed to write {frames_path:?}"))?;
+
+    // Blow small grids up so single cells are visible
+    let largest = frames.iter().map(|f| f.width.max(f.height)).max().unwrap();
+    let scale = (512 / largest.max(1)).clamp(1, 16) as u32;
+
+    let images = frames.iter().map(|frame| {
+        RgbImage::from_fn(
+            frame.width as u32 * scale,
+            frame.height as u32 * scale,
+            |x, y| {
+                let (x, y) = ((x / scale) as usize, (y / scale) as usize);
+                image::Rgb(frame.pixels[y * frame.width + x])
+            },
+        )
+    });
+
+    let gif_path = dir.with_extension("gif");
+    let gif = std::fs::File::create(&gif_path)
+        .wrap_err_with(|| format!("Failed to create {gif_path:?}"))?;
+    let mut encoder = GifEncoder::new_with_speed(std::io::BufWriter::new(gif), 10);
+    encoder.set_repeat(Repeat::Infinite)?;
+
+    for (i, image) in images.enumerate() {
+        let png_path = dir.join(format!("{i:04}.png"));
+        image
+            .save(&png_path)
+            .wrap_err_with(|| format!("Failed to write {png_path:?}"))?;
+
+        let image = RgbaImage::from(image::DynamicImage::ImageRgb8(image));
+        let delay = Delay::from_numer_denom_ms(100, 1);
+        encoder.encode_frame(image::Frame::from_parts(image, 0, 0, delay))?;
+    }
+    Ok(())
+}
+
+/// Every frame as `frame WIDTH HEIGHT COUNTERS`, then a `name\tvalue` line per counter and a line
+/// of `rrggbb` hex per row.
+#[cfg(feature = "viz")]
+fn frames_file(frames: &[Frame]) -> String {
+    use std::fmt::Write;
+
+    let mut file = String::new();
+    for frame in frames {
+        let _ = writeln!(
+            file,
+            "frame {} {} {}",
+            frame.width,
+            frame.height,
+            frame.counters.len()
+        );
+        for (name, value) in &frame.counters {
+            let _ = writeln!(file, "{name}\t{value}");
+        }
+        for row in frame.pixels.chunks(frame.width.max(1)) {
+            file.push_str(&hex::encode(row.as_flattened()));
+            file.push('\n');
+        }
+    }
+    file
+}
+
+#[cfg(not(feature = "viz"))]
+pub(crate) fn save(_dir: &std::path::Path) -> crate::Result<()> {
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use aoc::proptest::{collection, prelude::*};
+use aoc::*;
+
+#[parse(line)]
+fn parse_directions(input: &str) -> Result<i32> {
+    let (dir, amount) = input.split_at(1);
+    let amount = amount.parse::<i32>()? * if dir == "L" { -1 } else { 1 };
+    Ok(amount)
+}
+
+#[part_one]
+fn count_zeros(steps: &[i32]) -> i32 {
+    steps
+        .iter()
+        .fold((50, 0), |(dial, zeros), step| {
+            let new_dial = (dial + step).rem_euclid(100);
+            (new_dial, if new_dial == 0 { zeros + 1 } else { zeros })
+        })
+        .1
+}
+
+#[part_two]
+fn count_moves_past_zero(steps: &[i32]) -> i32 {
+    steps
+        .iter()
+        .fold((50, 0), |(dial, zeros), step| {
+            let new_dial = (dial + step).rem_euclid(100);
+            let to_reach_zero = if dial == 0 {
+                100
+            } else if *step > 0 {
+                100 - dial
+            } else {
+                dial
+            };
+            let remainder_step = step.abs() - to_reach_zero;
+            if remainder_step < 0 {
+                (new_dial, zeros) // Didn't cross zero this time
+            } else {
+                (new_dial, zeros + 1 + remainder_step / 100)
+            }
+        })
+        .1
+}
+
+/// Turns the dial one click at a time, counting rotations that end on zero and every click that
+/// lands on it
+fn click_through(steps: &[i32]) -> (i32, i32) {
+    let (mut dial, mut ends, mut clicks) = (50, 0, 0);
+    for step in steps {
+        for _ in 0..step.abs() {
+            dial = (dial + step.signum()).rem_euclid(100);
+            if dial == 0 {
+                clicks += 1;
+            }
+        }
+        if dial == 0 {
+            ends += 1;
+        }
+    }
+    (ends, clicks)
+}
+
+#[part_one(oracle)]
+fn count_zeros_by_clicking(steps: &[i32]) -> i32 {
+    click_through(steps).0
+}
+
+#[part_two(oracle)]
+fn count_moves_past_zero_by_clicking(steps: &[i32]) -> i32 {
+    click_through(steps).1
+}
+
+#[generator]
+fn random_rotations() -> impl Strategy<Value = String> {
+    collection::vec((prop_oneof!["L", "R"], 1..1000), 1..100).prop_map(|rotations| {
+        rotations
+            .iter()
+            .map(|(dir, amount)| format!("{dir}{amount}"))
+            .join("\n")
+    })
+}
+
+aoc_day!(1);
@@ -0,0 +1,196 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+};
+
+use aoc::proptest::{collection, prelude::*};
+use aoc::*;
+use kiddo::{KdTree, SquaredEuclidean};
+use union_find::{QuickFindUf, UnionBySize, UnionFind};
+
+type Point = (u32, u32, u32);
+type BoxMap = KdTree<f64, 3>;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct HeapEntry {
+    distance: ordered_float::OrderedFloat<f64>,
+    point_a: usize,
+    point_b: usize,
+}
+
+#[parse(lines)]
+fn parse_junction_boxes(input: Lines) -> Result<(Vec<Point>, BoxMap)> {
+    let mut points = vec![];
+    let mut tree = KdTree::new();
+    for (idx, line) in input.enumerate() {
+        let [x, y, z] = line
+            .trim()
+            .split(',')
+            .map(|coord| coord.parse::<f64>().unwrap())
+            .collect_array()
+            .unwrap();
+        points.push((x as u32, y as u32, z as u32));
+        tree.add(&[x, y, z], idx as u64);
+    }
+    Ok((points, tree))
+}
+
+/// Pairs of each box with its `neighbours` nearest boxes, along with how close a pair that was
+/// left out could be.
+fn get_sorted_pairs(
+    points: &[Point],
+    box_map: &BoxMap,
+    neighbours: usize,
+) -> (BinaryHeap<Reverse<HeapEntry>>, f64) {
+    let mut heap = BinaryHeap::new();
+    let mut seen = HashSet::new();
+    let mut reach = f64::INFINITY;
+    for (index, point) in points.iter().enumerate() {
+        // One extra for the box itself
+        let closest_neighbors = box_map.nearest_n::<SquaredEuclidean>(
+            &[point.0 as f64, point.1 as f64, point.2 as f64],
+            neighbours + 1,
+        );
+        // Any box left out is at least as far away as the furthest one found
+        if closest_neighbors.len() > neighbours {
+            reach = reach.min(closest_neighbors.last().unwrap().distance);
+        }
+
+        // Not necessarily first, when another box is in the same spot
+        for neighbor in closest_neighbors
+            .iter()
+            .filter(|n| n.item as usize != index)
+        {
+            // Lowest index first, so ties come out in the same order however a pair was found
+            let (point_a, point_b) = (
+                index.min(neighbor.item as usize),
+                index.max(neighbor.item as usize),
+            );
+            if !seen.insert((point_a, point_b)) {
+                continue;
+            }
+            heap.push(Reverse(HeapEntry {
+                distance: neighbor.distance.into(),
+                point_a,
+                point_b,
+            }));
+        }
+    }
+
+    (heap, reach)
+}
+
+/// Pairs of boxes, closest first. Starts from each box's nearest few neighbours and fetches more
+/// whenever the next pair could be further apart than one that was left out.
+fn closest_pairs<'a>(
+    points: &'a [Point],
+    box_map: &'a BoxMap,
+) -> impl Iterator<Item = (usize, usize)> + 'a {
+    let mut neighbours = 6;
+    let (mut heap, mut reach) = get_sorted_pairs(points, box_map, neighbours);
+    let mut taken = 0;
+
+    std::iter::from_fn(move || {
+        loop {
+            let Reverse(entry) = heap.peek()?;
+            if entry.distance.0 < reach {
+                let Reverse(entry) = heap.pop().unwrap();
+                taken += 1;
+                return Some((entry.point_a, entry.point_b));
+            }
+
+            // Everything closer than the old reach comes out the same, so skip what's been taken
+            neighbours *= 2;
+            (heap, reach) = get_sorted_pairs(points, box_map, neighbours);
+            for _ in 0..taken {
+                heap.pop();
+            }
+        }
+    })
+}
+
+#[part_one]
+fn connect_closest_boxes(points: &[Point], box_map: &BoxMap) -> u64 {
+    let mut union_find = QuickFindUf::<UnionBySize>::new(points.len());
+
+    const MAX_ITERATIONS: usize = 999;
+    for (point_a, point_b) in closest_pairs(points, box_map).take(MAX_ITERATIONS) {
+        union_find.union(point_a, point_b);
+    }
+
+    let mut circuit_sizes = vec![0; union_find.size()];
+
+    for id in 0..points.len() {
+        circuit_sizes[union_find.find(id)] += 1;
+    }
+
+    circuit_sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+    circuit_sizes[0] * circuit_sizes[1] * circuit_sizes[2]
+}
+
+#[part_two]
+fn connect_all(points: &[Point], box_map: &BoxMap) -> u64 {
+    let mut union_find = QuickFindUf::<UnionBySize>::new(points.len());
+
+    // Everything is connected after one fewer join than there are boxes
+    closest_pairs(points, box_map)
+        .filter(|&(point_a, point_b)| union_find.union(point_a, point_b))
+        .nth(points.len().saturating_sub(2))
+        .map_or(0, |(point_a, point_b)| {
+            points[point_a].0 as u64 * points[point_b].0 as u64
+        })
+}
+
+/// Every pair of boxes, closest first
+fn all_pairs_by_distance(points: &[Point]) -> Vec<(usize, usize)> {
+    let distance = |a: Point, b: Point| {
+        [(a.0, b.0), (a.1, b.1), (a.2, b.2)]
+            .iter()
+            .map(|&(a, b)| (a.abs_diff(b) as u64).pow(2))
+            .sum::<u64>()
+    };
+    (0..points.len())
+        .tuple_combinations()
+        .sorted_by_key(|&(a, b)| distance(points[a], points[b]))
+        .collect()
+}
+
+#[part_one(oracle)]
+fn connect_closest_boxes_exactly(points: &[Point], _box_map: &BoxMap) -> u64 {
+    let mut union_find = QuickFindUf::<UnionBySize>::new(points.len());
+    for (a, b) in all_pairs_by_distance(points).into_iter().take(999) {
+        union_find.union(a, b);
+    }
+
+    let mut circuit_sizes = vec![0; points.len()];
+    for id in 0..points.len() {
+        circuit_sizes[union_find.find(id)] += 1;
+    }
+    circuit_sizes.sort_unstable_by(|a, b| b.cmp(a));
+    circuit_sizes[0] * circuit_sizes[1] * circuit_sizes[2]
+}
+
+#[part_two(oracle)]
+fn connect_all_exactly(points: &[Point], _box_map: &BoxMap) -> u64 {
+    let mut union_find = QuickFindUf::<UnionBySize>::new(points.len());
+    all_pairs_by_distance(points)
+        .into_iter()
+        .filter(|&(a, b)| union_find.union(a, b))
+        .last()
+        .map_or(0, |(a, b)| points[a].0 as u64 * points[b].0 as u64)
+}
+
+/// Up to 1000 boxes spread out like the real input. Part one needs three circuits.
+#[generator]
+fn random_boxes() -> impl Strategy<Value = String> {
+    let coord = || 0..100_000u32;
+    collection::vec((coord(), coord(), coord()), 3..=1000).prop_map(|boxes| {
+        boxes
+            .iter()
+            .map(|(x, y, z)| format!("{x},{y},{z}"))
+            .join("\n")
+    })
+}
+
+aoc_day!(8);